@@ -13,10 +13,10 @@ fn main() {
   let mut controllers = vec![];
   let joystick_count = sdl.get_number_of_joysticks().unwrap();
   for n in 0 .. joystick_count {
-    controllers.push(sdl.open_controller(n).unwrap())
+    controllers.push(sdl.open_controller(JoystickDeviceIndex(n)).unwrap())
   }
   'main: loop {
-    while let Some((event, time)) = sdl.poll_event() {
+    for (event, time) in sdl.poll_events() {
       println!("[{time}] {event:?}", time = time, event = event);
       if matches!(event, Event::Quit) {
         break 'main;
@@ -6,7 +6,7 @@ use fermium::{SDL_AudioDeviceID, SDL_AudioSpec, SDL_OpenAudioDevice};
 
 use tinyvec::TinyVec;
 
-use crate::{sdl_get_error, Initialization, SdlError};
+use crate::{sdl_get_error, AudioDeviceID, Initialization, SdlError};
 
 pub struct AudioDevice {
   // TODO: NonZeroUWhatever?
@@ -22,6 +22,12 @@ impl Drop for AudioDevice {
     unsafe { fermium::SDL_CloseAudioDevice(self.device_id) }
   }
 }
+impl AudioDevice {
+  /// The ID of the open device, as used by `AudioDeviceEvent::Removed`.
+  pub fn device_id(&self) -> AudioDeviceID {
+    AudioDeviceID(self.device_id)
+  }
+}
 /// The Audio subsystem is the only part of SDL that **is** thread safe.
 unsafe impl Send for AudioDevice {}
 unsafe impl Sync for AudioDevice {}
@@ -101,6 +107,11 @@ pub struct AudioQueueRequestSpec {
 
 pub struct AudioQueueDevice(AudioDevice);
 impl AudioQueueDevice {
+  /// The ID of the open device, as used by `AudioDeviceEvent::Removed`.
+  pub fn device_id(&self) -> AudioDeviceID {
+    self.0.device_id()
+  }
+
   pub(crate) fn open(
     init: Arc<Initialization>, device_name: Option<&str>, capture: bool,
     spec: &AudioQueueRequestSpec, changes: AllowedAudioChanges,
@@ -167,6 +178,11 @@ pub struct AudioCallbackRequestSpec {
 
 pub struct AudioCallbackDevice(AudioDevice);
 impl AudioCallbackDevice {
+  /// The ID of the open device, as used by `AudioDeviceEvent::Removed`.
+  pub fn device_id(&self) -> AudioDeviceID {
+    self.0.device_id()
+  }
+
   pub(crate) unsafe fn open(
     init: Arc<Initialization>, device_name: Option<&str>, capture: bool,
     spec: &AudioCallbackRequestSpec, changes: AllowedAudioChanges,
@@ -0,0 +1,38 @@
+use core::convert::TryFrom;
+
+use fermium::{
+  SDL_BlendMode, SDL_BLENDMODE_ADD, SDL_BLENDMODE_BLEND, SDL_BLENDMODE_MOD,
+  SDL_BLENDMODE_MUL, SDL_BLENDMODE_NONE,
+};
+
+/// How the colors of a source are combined with the colors of a destination.
+///
+/// In the formulas, `src` and `dst` are color channels in the `0.0 ..= 1.0`
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlendMode {
+  /// `dst = src`
+  None = SDL_BLENDMODE_NONE as _,
+  /// `dst = src * src_a + dst * (1 - src_a)`
+  Blend = SDL_BLENDMODE_BLEND as _,
+  /// `dst = src * src_a + dst`
+  Add = SDL_BLENDMODE_ADD as _,
+  /// `dst = src * dst`
+  Mod = SDL_BLENDMODE_MOD as _,
+  /// `dst = src * dst + dst * (1 - src_a)` (>= SDL 2.0.12)
+  Mul = SDL_BLENDMODE_MUL as _,
+}
+impl TryFrom<SDL_BlendMode> for BlendMode {
+  type Error = ();
+  #[inline]
+  fn try_from(mode: SDL_BlendMode) -> Result<Self, Self::Error> {
+    Ok(match mode {
+      SDL_BLENDMODE_NONE => Self::None,
+      SDL_BLENDMODE_BLEND => Self::Blend,
+      SDL_BLENDMODE_ADD => Self::Add,
+      SDL_BLENDMODE_MOD => Self::Mod,
+      SDL_BLENDMODE_MUL => Self::Mul,
+      _ => return Err(()),
+    })
+  }
+}
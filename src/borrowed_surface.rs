@@ -0,0 +1,172 @@
+use core::{convert::TryInto, marker::PhantomData, ops::Deref, ptr::NonNull};
+
+use crate::{
+  sdl_get_error, BlendMode, Palette, PixelFormatEnum, Rect, SdlError, Surface,
+  SurfaceLock,
+};
+
+/// A [`Surface`] that uses pixel memory you already have, without copying it.
+///
+/// This derefs to `&Surface`, and has the `Surface` methods that need `&mut`
+/// of its own, so it can be blitted to and from, locked, and so on. The pixel
+/// memory stays borrowed for as long as it lives. Dropping it releases the
+/// surface and leaves your buffer alone.
+///
+/// There's no `&mut Surface` access, since that would let the inner surface
+/// be swapped out and outlive the buffer.
+pub struct BorrowedSurface<'b> {
+  surface: Surface,
+  _pixels: PhantomData<&'b mut [u8]>,
+}
+impl<'b> Deref for BorrowedSurface<'b> {
+  type Target = Surface;
+  fn deref(&self) -> &Self::Target {
+    &self.surface
+  }
+}
+impl<'b> BorrowedSurface<'b> {
+  /// Wraps a pixel buffer using color masks to describe the format.
+  ///
+  /// * `pitch` is the number of bytes between the start of each row.
+  /// * `masks` are the red, green, blue, and alpha masks, in that order.
+  ///
+  /// ## Panics
+  /// * If `pitch` is too small to hold `width` pixels at `bit_depth`.
+  /// * If `pixels` is too short to hold `height` rows at `pitch`.
+  pub fn from_pixels(
+    pixels: &'b mut [u8], width: usize, height: usize, bit_depth: usize,
+    pitch: usize, masks: [u32; 4],
+  ) -> Result<Self, SdlError> {
+    let [r_mask, g_mask, b_mask, a_mask] = masks;
+    check_buffer(pixels.len(), width, height, (bit_depth + 7) >> 3, pitch);
+    NonNull::new(unsafe {
+      fermium::SDL_CreateRGBSurfaceFrom(
+        pixels.as_mut_ptr().cast(),
+        width.try_into().unwrap(),
+        height.try_into().unwrap(),
+        bit_depth.try_into().unwrap(),
+        pitch.try_into().unwrap(),
+        r_mask,
+        g_mask,
+        b_mask,
+        a_mask,
+      )
+    })
+    .ok_or_else(sdl_get_error)
+    .map(|nn| BorrowedSurface { surface: Surface { nn }, _pixels: PhantomData })
+  }
+
+  /// Wraps a pixel buffer of the given pixel format.
+  ///
+  /// * `pitch` is the number of bytes between the start of each row.
+  ///
+  /// ## Panics
+  /// * If `pitch` is too small to hold `width` pixels of the format.
+  /// * If `pixels` is too short to hold `height` rows at `pitch`.
+  pub fn from_pixels_with_format(
+    pixels: &'b mut [u8], width: usize, height: usize, pitch: usize,
+    pixel_format: PixelFormatEnum,
+  ) -> Result<Self, SdlError> {
+    let bits_per_pixel = fermium::SDL_BITSPERPIXEL(pixel_format.0 as _);
+    let bytes_per_pixel = fermium::SDL_BYTESPERPIXEL(pixel_format.0 as _);
+    check_buffer(pixels.len(), width, height, bytes_per_pixel as usize, pitch);
+    NonNull::new(unsafe {
+      fermium::SDL_CreateRGBSurfaceWithFormatFrom(
+        pixels.as_mut_ptr().cast(),
+        width.try_into().unwrap(),
+        height.try_into().unwrap(),
+        bits_per_pixel as _,
+        pitch.try_into().unwrap(),
+        pixel_format.0,
+      )
+    })
+    .ok_or_else(sdl_get_error)
+    .map(|nn| BorrowedSurface { surface: Surface { nn }, _pixels: PhantomData })
+  }
+}
+
+fn check_buffer(
+  len: usize, width: usize, height: usize, bytes_per_pixel: usize, pitch: usize,
+) {
+  assert!(pitch >= width * bytes_per_pixel, "pitch is too small");
+  assert!(len >= pitch * height, "pixel buffer is too small");
+}
+
+impl<'b> BorrowedSurface<'b> {
+  /// See [`Surface::blit`].
+  pub fn blit(
+    &mut self, src: &Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+  ) -> Result<Rect, SdlError> {
+    self.surface.blit(src, src_rect, dst_rect)
+  }
+
+  /// See [`Surface::blit_scaled`].
+  pub fn blit_scaled(
+    &mut self, src: &Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+  ) -> Result<Rect, SdlError> {
+    self.surface.blit_scaled(src, src_rect, dst_rect)
+  }
+
+  /// Stretches all or part of `src` onto this surface, see
+  /// [`Surface::stretch_into`].
+  pub fn stretch_from(
+    &mut self, src: &Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+    linear: bool,
+  ) -> Result<(), SdlError> {
+    src.stretch_into(&mut self.surface, src_rect, dst_rect, linear)
+  }
+
+  /// See [`Surface::set_clip_rect`].
+  pub fn set_clip_rect(&mut self, clip: Option<Rect>) -> bool {
+    self.surface.set_clip_rect(clip)
+  }
+
+  /// See [`Surface::set_color_key`].
+  pub fn set_color_key(
+    &mut self, key: Option<[u8; 4]>,
+  ) -> Result<(), SdlError> {
+    self.surface.set_color_key(key)
+  }
+
+  /// See [`Surface::set_alpha_mod`].
+  pub fn set_alpha_mod(&mut self, alpha: u8) -> Result<(), SdlError> {
+    self.surface.set_alpha_mod(alpha)
+  }
+
+  /// See [`Surface::set_color_mod`].
+  pub fn set_color_mod(&mut self, rgb: [u8; 3]) -> Result<(), SdlError> {
+    self.surface.set_color_mod(rgb)
+  }
+
+  /// See [`Surface::set_blend_mode`].
+  pub fn set_blend_mode(&mut self, mode: BlendMode) -> Result<(), SdlError> {
+    self.surface.set_blend_mode(mode)
+  }
+
+  /// See [`Surface::set_rle`].
+  pub fn set_rle(&mut self, enabled: bool) -> Result<(), SdlError> {
+    self.surface.set_rle(enabled)
+  }
+
+  /// See [`Surface::set_palette`].
+  pub fn set_palette(&mut self, palette: &Palette) -> Result<(), SdlError> {
+    self.surface.set_palette(palette)
+  }
+
+  /// See [`Surface::write_pixel`].
+  pub fn write_pixel(
+    &mut self, x: usize, y: usize, rgba: [u8; 4],
+  ) -> Result<(), SdlError> {
+    self.surface.write_pixel(x, y, rgba)
+  }
+
+  /// See [`Surface::lock`].
+  pub fn lock(&mut self) -> Result<SurfaceLock<'_>, SdlError> {
+    self.surface.lock()
+  }
+
+  /// See [`Surface::premultiply_alpha`].
+  pub fn premultiply_alpha(&mut self) -> Result<(), SdlError> {
+    self.surface.premultiply_alpha()
+  }
+}
@@ -0,0 +1,191 @@
+use alloc::{boxed::Box, string::String};
+
+use crate::{FlipMode, Rect, RectF, RendererWindow, SdlError, Texture};
+
+/// A 2D view transform layered over a [`RendererWindow`].
+///
+/// The camera maps "world" coordinates (whatever units your game uses) to
+/// "screen" coordinates (the renderer's logical size if one is set, otherwise
+/// its output size in pixels).
+///
+/// * `offset` is the world position shown at the center of the view.
+/// * `zoom` is how many screen units one world unit covers.
+/// * Rotation turns the view clockwise around its center. Setting it needs
+///   `sin`/`cos`, so it's only available with the `std` feature.
+///
+/// Call [`fit_to`](Self::fit_to) whenever the window is resized or the logical
+/// size changes, so that the camera knows where the center of the view is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+  pub offset: [f32; 2],
+  pub zoom: f32,
+  angle: f32,
+  cos_sin: [f32; 2],
+  view_size: [f32; 2],
+}
+impl Default for Camera2D {
+  fn default() -> Self {
+    Self {
+      offset: [0.0, 0.0],
+      zoom: 1.0,
+      angle: 0.0,
+      cos_sin: [1.0, 0.0],
+      view_size: [0.0, 0.0],
+    }
+  }
+}
+impl Camera2D {
+  /// Makes a new camera with no rotation.
+  pub fn new(offset: [f32; 2], zoom: f32, view_size: [u32; 2]) -> Self {
+    let mut out = Self { offset, zoom, ..Self::default() };
+    out.set_view_size(view_size);
+    out
+  }
+
+  /// Sets the size of the screen space that the camera draws into.
+  pub fn set_view_size(&mut self, [w, h]: [u32; 2]) {
+    self.view_size = [w as f32, h as f32];
+  }
+
+  /// Sets the view size from the renderer's logical size, or its output size
+  /// if no logical size is set.
+  pub fn fit_to(&mut self, rend_win: &RendererWindow) -> Result<(), SdlError> {
+    let size = match rend_win.logical_size() {
+      Some(size) => size,
+      None => rend_win.output_size()?,
+    };
+    self.set_view_size(size);
+    Ok(())
+  }
+
+  /// The view rotation, in degrees clockwise.
+  pub fn rotation(&self) -> f32 {
+    self.angle
+  }
+
+  /// Sets the view rotation, in degrees clockwise.
+  #[cfg(feature = "std")]
+  pub fn set_rotation(&mut self, degrees: f32) {
+    let radians = degrees.to_radians();
+    self.angle = degrees;
+    self.cos_sin = [radians.cos(), radians.sin()];
+  }
+
+  /// Converts a world position to a screen position.
+  pub fn world_to_screen(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+    let [c, s] = self.cos_sin;
+    let dx = (x - self.offset[0]) * self.zoom;
+    let dy = (y - self.offset[1]) * self.zoom;
+    [
+      self.view_size[0] / 2.0 + (c * dx - s * dy),
+      self.view_size[1] / 2.0 + (s * dx + c * dy),
+    ]
+  }
+
+  /// Converts a screen position (eg: from a mouse event) to a world position.
+  ///
+  /// This is the exact inverse of [`world_to_screen`](Self::world_to_screen),
+  /// so it can be used for picking.
+  pub fn screen_to_world(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+    let [c, s] = self.cos_sin;
+    let sx = x - self.view_size[0] / 2.0;
+    let sy = y - self.view_size[1] / 2.0;
+    [
+      self.offset[0] + (c * sx + s * sy) / self.zoom,
+      self.offset[1] + (-s * sx + c * sy) / self.zoom,
+    ]
+  }
+
+  /// The axis-aligned world area that's visible on screen.
+  ///
+  /// When the view is rotated this is the bounding box of the visible area, so
+  /// it's still correct to use for culling.
+  pub fn visible_world_rect(&self) -> RectF {
+    let [w, h] = self.view_size;
+    let corners = [
+      self.screen_to_world([0.0, 0.0]),
+      self.screen_to_world([w, 0.0]),
+      self.screen_to_world([0.0, h]),
+      self.screen_to_world([w, h]),
+    ];
+    let mut min = corners[0];
+    let mut max = corners[0];
+    for [x, y] in corners.iter().copied() {
+      min = [min[0].min(x), min[1].min(y)];
+      max = [max[0].max(x), max[1].max(y)];
+    }
+    RectF::new(min[0], min[1], max[0] - min[0], max[1] - min[1])
+  }
+
+  /// Copies part of a texture to a rectangle given in world space.
+  pub fn copy(
+    &self, rend_win: &RendererWindow, texture: &Texture, src: Option<Rect>,
+    dst: RectF,
+  ) -> Result<(), SdlError> {
+    self.copy_ex(rend_win, texture, src, dst, 0.0, FlipMode::None)
+  }
+
+  /// As [`copy`](Self::copy), but the texture is also rotated (degrees,
+  /// clockwise) around the center of `dst` and optionally flipped.
+  pub fn copy_ex(
+    &self, rend_win: &RendererWindow, texture: &Texture, src: Option<Rect>,
+    dst: RectF, angle: f64, flip: FlipMode,
+  ) -> Result<(), SdlError> {
+    let [cx, cy] =
+      self.world_to_screen([dst.x + dst.w / 2.0, dst.y + dst.h / 2.0]);
+    let w = dst.w * self.zoom;
+    let h = dst.h * self.zoom;
+    let screen = RectF::new(cx - w / 2.0, cy - h / 2.0, w, h);
+    rend_win.copy_ex_f(
+      texture,
+      src,
+      Some(screen),
+      angle + f64::from(self.angle),
+      flip,
+    )
+  }
+
+  /// Draws a point given in world space.
+  pub fn draw_point(
+    &self, rend_win: &RendererWindow, p: [f32; 2],
+  ) -> Result<(), SdlError> {
+    rend_win.draw_point_f(self.world_to_screen(p))
+  }
+
+  /// Draws a line between two world space points.
+  pub fn draw_line(
+    &self, rend_win: &RendererWindow, a: [f32; 2], b: [f32; 2],
+  ) -> Result<(), SdlError> {
+    rend_win.draw_line_f(self.world_to_screen(a), self.world_to_screen(b))
+  }
+
+  /// Draws the outline of a world space rectangle.
+  ///
+  /// This is correct even when the view is rotated.
+  pub fn draw_rect(
+    &self, rend_win: &RendererWindow, r: RectF,
+  ) -> Result<(), SdlError> {
+    let p0 = self.world_to_screen([r.x, r.y]);
+    let p1 = self.world_to_screen([r.x + r.w, r.y]);
+    let p2 = self.world_to_screen([r.x + r.w, r.y + r.h]);
+    let p3 = self.world_to_screen([r.x, r.y + r.h]);
+    rend_win.draw_lines_f(&[p0, p1, p2, p3, p0])
+  }
+
+  /// Fills a world space rectangle.
+  ///
+  /// ## Failure
+  /// * The renderer can only fill axis-aligned rectangles, so this is an error
+  ///   when the view is rotated.
+  pub fn fill_rect(
+    &self, rend_win: &RendererWindow, r: RectF,
+  ) -> Result<(), SdlError> {
+    if self.cos_sin != [1.0, 0.0] {
+      return Err(SdlError(Box::new(String::from(
+        "beryllium: can't fill a rect with a rotated camera.",
+      ))));
+    }
+    let [x, y] = self.world_to_screen([r.x, r.y]);
+    rend_win.fill_rect_f(RectF::new(x, y, r.w * self.zoom, r.h * self.zoom))
+  }
+}
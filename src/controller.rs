@@ -1,10 +1,53 @@
 use core::ptr::NonNull;
 
-use alloc::sync::Arc;
+use alloc::{string::String, sync::Arc};
 
-use fermium::SDL_GameController;
+use fermium::{
+  SDL_GameController, SDL_GameControllerType,
+  SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_PRO, SDL_CONTROLLER_TYPE_PS3,
+  SDL_CONTROLLER_TYPE_PS4, SDL_CONTROLLER_TYPE_XBOX360,
+  SDL_CONTROLLER_TYPE_XBOXONE,
+};
 
-use crate::{sdl_get_error, Initialization, SdlError};
+use crate::{
+  sdl_get_error, string_from_sdl, take_string_from_sdl, Initialization,
+  JoystickDeviceIndex, JoystickGuid, JoystickID, PowerLevel, SdlError,
+};
+
+// Note: these are from SDL 2.0.14, so fermium doesn't have them yet.
+const SDL_CONTROLLER_TYPE_VIRTUAL: SDL_GameControllerType = 6;
+const SDL_CONTROLLER_TYPE_PS5: SDL_GameControllerType = 7;
+
+/// The kind of controller, for showing the right button pictures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ControllerType {
+  Unknown,
+  Xbox360,
+  XboxOne,
+  PS3,
+  PS4,
+  /// Needs SDL 2.0.14 or later at runtime.
+  PS5,
+  SwitchPro,
+  /// A virtual controller made by the program. Needs SDL 2.0.14 or later at
+  /// runtime.
+  Virtual,
+}
+impl From<SDL_GameControllerType> for ControllerType {
+  #[inline]
+  fn from(controller_type: SDL_GameControllerType) -> Self {
+    match controller_type {
+      SDL_CONTROLLER_TYPE_XBOX360 => Self::Xbox360,
+      SDL_CONTROLLER_TYPE_XBOXONE => Self::XboxOne,
+      SDL_CONTROLLER_TYPE_PS3 => Self::PS3,
+      SDL_CONTROLLER_TYPE_PS4 => Self::PS4,
+      SDL_CONTROLLER_TYPE_PS5 => Self::PS5,
+      SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_PRO => Self::SwitchPro,
+      SDL_CONTROLLER_TYPE_VIRTUAL => Self::Virtual,
+      _ => Self::Unknown,
+    }
+  }
+}
 
 pub struct Controller {
   nn: NonNull<SDL_GameController>,
@@ -21,10 +64,185 @@ impl Drop for Controller {
 }
 impl Controller {
   pub(crate) fn open(
-    init: Arc<Initialization>, id: usize,
+    init: Arc<Initialization>, index: JoystickDeviceIndex,
   ) -> Result<Self, SdlError> {
-    NonNull::new(unsafe { fermium::SDL_GameControllerOpen(id as i32) })
+    let index = index.to_sdl()?;
+    NonNull::new(unsafe { fermium::SDL_GameControllerOpen(index) })
       .ok_or_else(sdl_get_error)
       .map(|nn| Controller { init, nn })
   }
+
+  /// Gets another handle to a controller that's already open.
+  pub(crate) fn from_instance_id(
+    init: Arc<Initialization>, id: JoystickID,
+  ) -> Option<Self> {
+    let already_open =
+      unsafe { fermium::SDL_GameControllerFromInstanceID(id.0) };
+    if already_open.is_null() {
+      return None;
+    }
+    // Note: SDL doesn't add a reference when it looks up by instance
+    // ID, but opening the same device again does, and gives the same pointer.
+    // That way each handle can close the controller when it drops.
+    let device_count = unsafe { fermium::SDL_NumJoysticks() };
+    let index = (0..device_count).find(|&index| {
+      let device_id =
+        unsafe { fermium::SDL_JoystickGetDeviceInstanceID(index) };
+      device_id == id.0
+    })?;
+    Self::open(init, JoystickDeviceIndex(index as usize)).ok()
+  }
+
+  /// The instance ID of the joystick behind this controller.
+  ///
+  /// Controller events (including `ControllerDeviceEvent::Removed`) use this
+  /// to say which controller they're about.
+  pub fn joystick_id(&self) -> JoystickID {
+    JoystickID(unsafe {
+      fermium::SDL_JoystickInstanceID(fermium::SDL_GameControllerGetJoystick(
+        self.nn.as_ptr(),
+      ))
+    })
+  }
+
+  /// The instance ID of this controller, which is the same as its
+  /// [`joystick_id`](Controller::joystick_id).
+  ///
+  /// Use [`Sdl::controller_from_instance_id`](crate::Sdl::controller_from_instance_id)
+  /// to go the other way.
+  pub fn instance_id(&self) -> JoystickID {
+    self.joystick_id()
+  }
+
+  /// The GUID of this kind of controller, as used in mapping strings.
+  pub fn guid(&self) -> JoystickGuid {
+    JoystickGuid(
+      unsafe {
+        fermium::SDL_JoystickGetGUID(fermium::SDL_GameControllerGetJoystick(
+          self.nn.as_ptr(),
+        ))
+      }
+      .data,
+    )
+  }
+
+  /// The name of the controller, if it has one.
+  pub fn name(&self) -> Option<String> {
+    let p = unsafe { fermium::SDL_GameControllerName(self.nn.as_ptr()) };
+    if p.is_null() {
+      None
+    } else {
+      Some(unsafe { string_from_sdl(p.cast()) })
+    }
+  }
+
+  /// If the controller is still plugged in.
+  ///
+  /// Once this is `false` it stays that way, even if the controller is
+  /// plugged back in (that comes as a new controller).
+  pub fn is_attached(&self) -> bool {
+    let attached =
+      unsafe { fermium::SDL_GameControllerGetAttached(self.nn.as_ptr()) };
+    attached == fermium::SDL_TRUE
+  }
+
+  /// The battery level, such as for a low battery warning.
+  ///
+  /// Many controllers don't report this, and give `Unknown`.
+  pub fn power_level(&self) -> PowerLevel {
+    PowerLevel::from(unsafe {
+      fermium::SDL_JoystickCurrentPowerLevel(
+        fermium::SDL_GameControllerGetJoystick(self.nn.as_ptr()),
+      )
+    })
+  }
+
+  /// The kind of controller this is.
+  pub fn controller_type(&self) -> ControllerType {
+    ControllerType::from(unsafe {
+      fermium::SDL_GameControllerGetType(self.nn.as_ptr())
+    })
+  }
+
+  /// The USB vendor ID of the controller, if it's known.
+  pub fn vendor(&self) -> Option<u16> {
+    let id = unsafe { fermium::SDL_GameControllerGetVendor(self.nn.as_ptr()) };
+    if id != 0 {
+      Some(id)
+    } else {
+      None
+    }
+  }
+
+  /// The USB product ID of the controller, if it's known.
+  pub fn product(&self) -> Option<u16> {
+    let id = unsafe { fermium::SDL_GameControllerGetProduct(self.nn.as_ptr()) };
+    if id != 0 {
+      Some(id)
+    } else {
+      None
+    }
+  }
+
+  /// The product version of the controller, if it's known.
+  pub fn product_version(&self) -> Option<u16> {
+    let version =
+      unsafe { fermium::SDL_GameControllerGetProductVersion(self.nn.as_ptr()) };
+    if version != 0 {
+      Some(version)
+    } else {
+      None
+    }
+  }
+
+  // TODO: `serial` once we bind SDL 2.0.14, and `firmware_version` once we
+  // bind SDL 2.0.24.
+
+  /// The mapping string that SDL uses for this controller.
+  ///
+  /// The format is the same as for
+  /// [`Sdl::add_controller_mapping`](crate::Sdl::add_controller_mapping).
+  pub fn mapping(&self) -> Option<String> {
+    unsafe {
+      take_string_from_sdl(
+        fermium::SDL_GameControllerMapping(self.nn.as_ptr()).cast(),
+      )
+    }
+  }
+
+  /// The player number of this controller, if it has one.
+  ///
+  /// On some controllers (such as Xbox pads) this is shown on the LEDs.
+  pub fn player_index(&self) -> Option<usize> {
+    let index =
+      unsafe { fermium::SDL_GameControllerGetPlayerIndex(self.nn.as_ptr()) };
+    if index >= 0 {
+      Some(index as usize)
+    } else {
+      None
+    }
+  }
+
+  /// Sets (or clears, with `None`) the player number of this controller.
+  pub fn set_player_index(&self, index: Option<usize>) {
+    let index = index.map_or(-1, |index| index as i32);
+    unsafe {
+      fermium::SDL_GameControllerSetPlayerIndex(self.nn.as_ptr(), index)
+    }
+  }
+
+  // TODO: Trigger rumble (`SDL_GameControllerRumbleTriggers`) and a
+  // `has_rumble_triggers` query, once we bind SDL 2.0.14. The query itself is
+  // `SDL_GameControllerHasRumbleTriggers` in 2.0.18.
+
+  // TODO: `set_led` and `has_led` (`SDL_GameControllerSetLED` and
+  // `SDL_GameControllerHasLED`), once we bind SDL 2.0.14.
+
+  // TODO: Touchpads (`SDL_GameControllerGetNumTouchpads`,
+  // `SDL_GameControllerGetNumTouchpadFingers`, and
+  // `SDL_GameControllerGetTouchpadFinger`), once we bind SDL 2.0.14.
+
+  // TODO: Motion sensors (`has_sensor`, `set_sensor_enabled`, `sensor_data`,
+  // and `sensor_data_rate` for the gyro and accelerometer), once we bind SDL
+  // 2.0.14. The data rate query is from 2.0.16.
 }
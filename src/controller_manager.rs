@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+use crate::{
+  Controller, ControllerDeviceEvent, Event, JoystickID, Sdl, SdlError,
+};
+
+/// A change to the controllers of a [`ControllerManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ControllerSlotChange {
+  /// A controller was opened and put in this player slot.
+  Connected { slot: usize, joystick_id: JoystickID },
+  /// The controller in this player slot was unplugged and closed.
+  Disconnected { slot: usize, joystick_id: JoystickID },
+}
+
+/// Opens and closes controllers as they're plugged in and unplugged, giving
+/// each one a player slot.
+///
+/// Pass every event to [`handle_event`](ControllerManager::handle_event).
+///
+/// * A new controller gets the lowest free slot, starting from 0, and its
+///   player index is set to match (which lights up the LEDs on some pads).
+/// * When a controller is unplugged its slot is left empty, so that the
+///   other players keep their slots. The next controller to be plugged in
+///   fills the gap.
+/// * Controllers already attached when SDL starts are sent as "added" events
+///   too, so they're picked up without any extra work.
+/// * Removals are matched by instance ID, never by device index, since
+///   device indexes shift around as devices come and go.
+#[derive(Default)]
+pub struct ControllerManager {
+  slots: Vec<Option<Controller>>,
+}
+impl core::fmt::Debug for ControllerManager {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_map()
+      .entries(self.controllers().map(|(slot, c)| (slot, c.joystick_id())))
+      .finish()
+  }
+}
+impl ControllerManager {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Opens or closes controllers because of an event.
+  ///
+  /// Gives the change, if there was one.
+  ///
+  /// ## Failure
+  /// * If a newly added controller can't be opened.
+  pub fn handle_event(
+    &mut self, sdl: &Sdl, event: &Event,
+  ) -> Result<Option<ControllerSlotChange>, SdlError> {
+    match event {
+      Event::ControllerDevice(ControllerDeviceEvent::Added {
+        device_index,
+      }) => {
+        let controller = sdl.open_controller(*device_index)?;
+        let joystick_id = controller.joystick_id();
+        // Note: If we already have this one (eg: it was added twice)
+        // the extra handle just gets dropped.
+        if self.slot_of(joystick_id).is_some() {
+          return Ok(None);
+        }
+        let slot = match self.slots.iter().position(Option::is_none) {
+          Some(slot) => slot,
+          None => {
+            self.slots.push(None);
+            self.slots.len() - 1
+          }
+        };
+        controller.set_player_index(Some(slot));
+        self.slots[slot] = Some(controller);
+        Ok(Some(ControllerSlotChange::Connected { slot, joystick_id }))
+      }
+      Event::ControllerDevice(ControllerDeviceEvent::Removed {
+        joystick_id,
+      }) => {
+        let joystick_id = *joystick_id;
+        Ok(self.slot_of(joystick_id).map(|slot| {
+          self.slots[slot] = None;
+          ControllerSlotChange::Disconnected { slot, joystick_id }
+        }))
+      }
+      _ => Ok(None),
+    }
+  }
+
+  /// The controller in a player slot, if there is one.
+  pub fn controller(&self, slot: usize) -> Option<&Controller> {
+    self.slots.get(slot).and_then(Option::as_ref)
+  }
+
+  /// The player slot of the controller with the given instance ID, such as
+  /// from a controller button event.
+  pub fn slot_of(&self, joystick_id: JoystickID) -> Option<usize> {
+    self
+      .controllers()
+      .find(|(_, c)| c.joystick_id() == joystick_id)
+      .map(|(slot, _)| slot)
+  }
+
+  /// All connected controllers, with their player slots, in slot order.
+  pub fn controllers(&self) -> impl Iterator<Item = (usize, &Controller)> + '_ {
+    self
+      .slots
+      .iter()
+      .enumerate()
+      .filter_map(|(slot, c)| c.as_ref().map(|c| (slot, c)))
+  }
+
+  /// How many controllers are connected.
+  pub fn connected_count(&self) -> usize {
+    self.controllers().count()
+  }
+}
@@ -0,0 +1,59 @@
+use crate::{
+  sdl_get_error, yuv_frame_len, yuv_pitch, PixelFormatEnum, SdlError,
+};
+
+/// Converts a block of pixels from one format to another.
+///
+/// * `src_pitch` and `dst_pitch` are the bytes between the start of each row.
+///   For YUV formats this is the pitch of the Y plane, see [`yuv_frame_len`].
+/// * `size` is the width and height in pixels of the area to convert.
+///
+/// ## Failure
+/// * If SDL can't convert between the two formats (eg: indexed formats, which
+///   would need a palette).
+///
+/// ## Panics
+/// * If a YUV pitch is too small to hold a row of `size`.
+/// * If either buffer is too short for `size` at its pitch.
+pub fn convert_pixels(
+  src_format: PixelFormatEnum, src: &[u8], src_pitch: usize,
+  dst_format: PixelFormatEnum, dst: &mut [u8], dst_pitch: usize,
+  [width, height]: [usize; 2],
+) -> Result<(), SdlError> {
+  assert!(src.len() >= min_len(src_format, src_pitch, width, height));
+  assert!(dst.len() >= min_len(dst_format, dst_pitch, width, height));
+  let ret = unsafe {
+    fermium::SDL_ConvertPixels(
+      width as _,
+      height as _,
+      src_format.0,
+      src.as_ptr().cast(),
+      src_pitch as _,
+      dst_format.0,
+      dst.as_mut_ptr().cast(),
+      dst_pitch as _,
+    )
+  };
+  if ret >= 0 {
+    Ok(())
+  } else {
+    Err(sdl_get_error())
+  }
+}
+
+/// Bytes that SDL will touch converting `width` by `height` pixels at `pitch`.
+fn min_len(
+  format: PixelFormatEnum, pitch: usize, width: usize, height: usize,
+) -> usize {
+  if let Some(min_pitch) = yuv_pitch(format, width) {
+    assert!(pitch >= min_pitch, "YUV pitch is too small");
+  }
+  if height == 0 {
+    return 0;
+  }
+  yuv_frame_len(format, pitch, height).unwrap_or_else(|| {
+    let row_bits = width * fermium::SDL_BITSPERPIXEL(format.0 as _) as usize;
+    let row_bytes = (row_bits + 7) >> 3;
+    pitch * (height - 1) + row_bytes
+  })
+}
@@ -0,0 +1,175 @@
+use core::cell::Cell;
+
+use fermium::{SDL_FRect, SDL_Renderer};
+
+/// How many frames of history the frame time graph shows.
+const HISTORY: usize = 120;
+
+/// Frame times at or above this many milliseconds fill the graph's height.
+const GRAPH_MAX_MS: f32 = 50.0;
+
+/// A snapshot of the renderer's bookkeeping counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderStats {
+  /// Copy and primitive calls made during the last completed frame.
+  pub draw_calls: u32,
+  /// Rough number of bytes of pixel data in all live textures.
+  ///
+  /// This is just width * height * bytes per pixel, the driver will generally
+  /// use some amount more than this.
+  pub texture_bytes: usize,
+  /// Milliseconds between the last two calls to `present`.
+  pub frame_time_ms: f32,
+}
+
+/// The counters that the renderer updates as it works, along with the state
+/// of the overlay that displays them.
+pub(crate) struct RenderCounters {
+  draw_calls: Cell<u32>,
+  last_draw_calls: Cell<u32>,
+  texture_bytes: Cell<usize>,
+  last_present: Cell<u64>,
+  frame_times: Cell<[f32; HISTORY]>,
+  next_frame: Cell<usize>,
+  overlay_enabled: Cell<bool>,
+}
+impl Default for RenderCounters {
+  fn default() -> Self {
+    Self {
+      draw_calls: Cell::new(0),
+      last_draw_calls: Cell::new(0),
+      texture_bytes: Cell::new(0),
+      last_present: Cell::new(0),
+      frame_times: Cell::new([0.0; HISTORY]),
+      next_frame: Cell::new(0),
+      overlay_enabled: Cell::new(false),
+    }
+  }
+}
+impl RenderCounters {
+  #[inline]
+  pub(crate) fn count_draw_call(&self) {
+    self.draw_calls.set(self.draw_calls.get().wrapping_add(1));
+  }
+
+  pub(crate) fn add_texture_bytes(&self, bytes: usize) {
+    self.texture_bytes.set(self.texture_bytes.get().saturating_add(bytes));
+  }
+
+  pub(crate) fn remove_texture_bytes(&self, bytes: usize) {
+    self.texture_bytes.set(self.texture_bytes.get().saturating_sub(bytes));
+  }
+
+  pub(crate) fn overlay_enabled(&self) -> bool {
+    self.overlay_enabled.get()
+  }
+
+  pub(crate) fn set_overlay_enabled(&self, enabled: bool) {
+    self.overlay_enabled.set(enabled)
+  }
+
+  pub(crate) fn stats(&self) -> RenderStats {
+    let frames = self.frame_times.get();
+    let last = (self.next_frame.get() + HISTORY - 1) % HISTORY;
+    RenderStats {
+      draw_calls: self.last_draw_calls.get(),
+      texture_bytes: self.texture_bytes.get(),
+      frame_time_ms: frames[last],
+    }
+  }
+
+  /// Called just before the renderer presents.
+  ///
+  /// Records the frame time, draws the overlay if it's enabled, and resets the
+  /// per-frame counters.
+  pub(crate) fn end_frame(&self, rend: *mut SDL_Renderer) {
+    let now = unsafe { fermium::SDL_GetPerformanceCounter() };
+    let last = self.last_present.replace(now);
+    if last != 0 {
+      let freq = unsafe { fermium::SDL_GetPerformanceFrequency() };
+      let ms = (now - last) as f64 * 1000.0 / freq as f64;
+      let mut frames = self.frame_times.get();
+      let i = self.next_frame.get();
+      frames[i] = ms as f32;
+      self.frame_times.set(frames);
+      self.next_frame.set((i + 1) % HISTORY);
+    }
+    self.last_draw_calls.set(self.draw_calls.replace(0));
+    if self.overlay_enabled.get() {
+      unsafe { self.draw_overlay(rend) };
+    }
+  }
+
+  /// Draws the overlay in the upper left of the render target.
+  ///
+  /// * A graph of recent frame times, with a marker line at 60fps.
+  /// * A bar with one pixel per draw call.
+  /// * A bar with one pixel per MiB of texture memory.
+  ///
+  /// None of this goes through the counted drawing methods, and the draw color
+  /// and blend mode are restored afterwards.
+  unsafe fn draw_overlay(&self, rend: *mut SDL_Renderer) {
+    use fermium::{
+      SDL_GetRenderDrawBlendMode, SDL_GetRenderDrawColor, SDL_RenderDrawLineF,
+      SDL_RenderFillRectF, SDL_SetRenderDrawBlendMode, SDL_SetRenderDrawColor,
+    };
+    const LEFT: f32 = 4.0;
+    const TOP: f32 = 4.0;
+    const GRAPH_H: f32 = 60.0;
+    const BAR_H: f32 = 6.0;
+    let width = HISTORY as f32 * 2.0;
+
+    let (mut r, mut g, mut b, mut a) = (0, 0, 0, 0);
+    SDL_GetRenderDrawColor(rend, &mut r, &mut g, &mut b, &mut a);
+    let mut blend = 0;
+    SDL_GetRenderDrawBlendMode(rend, &mut blend);
+    SDL_SetRenderDrawBlendMode(rend, fermium::SDL_BLENDMODE_BLEND);
+
+    // backdrop
+    SDL_SetRenderDrawColor(rend, 0, 0, 0, 180);
+    let backdrop = SDL_FRect {
+      x: LEFT - 2.0,
+      y: TOP - 2.0,
+      w: width + 4.0,
+      h: GRAPH_H + BAR_H * 2.0 + 8.0,
+    };
+    SDL_RenderFillRectF(rend, &backdrop);
+
+    // frame times, oldest on the left
+    SDL_SetRenderDrawColor(rend, 80, 220, 80, 255);
+    let frames = self.frame_times.get();
+    let start = self.next_frame.get();
+    for n in 0..HISTORY {
+      let ms = frames[(start + n) % HISTORY].min(GRAPH_MAX_MS);
+      let h = ms / GRAPH_MAX_MS * GRAPH_H;
+      let x = LEFT + n as f32 * 2.0;
+      let bar = SDL_FRect { x, y: TOP + GRAPH_H - h, w: 2.0, h };
+      SDL_RenderFillRectF(rend, &bar);
+    }
+    SDL_SetRenderDrawColor(rend, 220, 220, 80, 255);
+    let y_60fps = TOP + GRAPH_H - (1000.0 / 60.0) / GRAPH_MAX_MS * GRAPH_H;
+    SDL_RenderDrawLineF(rend, LEFT, y_60fps, LEFT + width, y_60fps);
+
+    // draw calls
+    SDL_SetRenderDrawColor(rend, 80, 160, 240, 255);
+    let calls = (self.last_draw_calls.get() as f32).min(width);
+    let bar = SDL_FRect { x: LEFT, y: TOP + GRAPH_H + 2.0, w: calls, h: BAR_H };
+    SDL_RenderFillRectF(rend, &bar);
+
+    // texture memory
+    SDL_SetRenderDrawColor(rend, 240, 120, 80, 255);
+    let mib = (self.texture_bytes.get() as f32 / (1024.0 * 1024.0)).min(width);
+    let bar =
+      SDL_FRect { x: LEFT, y: TOP + GRAPH_H + BAR_H + 4.0, w: mib, h: BAR_H };
+    SDL_RenderFillRectF(rend, &bar);
+
+    SDL_SetRenderDrawBlendMode(rend, blend);
+    SDL_SetRenderDrawColor(rend, r, g, b, a);
+  }
+}
+
+/// Estimated bytes of pixel data for a texture of the given format and size.
+pub(crate) fn texture_bytes(format: u32, w: u32, h: u32) -> usize {
+  let bpp = fermium::SDL_BYTESPERPIXEL(format as _) as usize;
+  (w as usize) * (h as usize) * bpp.max(1)
+}
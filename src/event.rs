@@ -1,32 +1,46 @@
 use core::convert::{TryFrom, TryInto};
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use fermium::{
-  SDL_Event, SDL_EventType, SDL_AUDIODEVICEADDED, SDL_AUDIODEVICEREMOVED,
+  SDL_Event, SDL_EventType, SDL_APP_DIDENTERBACKGROUND,
+  SDL_APP_DIDENTERFOREGROUND, SDL_APP_LOWMEMORY, SDL_APP_TERMINATING,
+  SDL_APP_WILLENTERBACKGROUND, SDL_APP_WILLENTERFOREGROUND,
+  SDL_AUDIODEVICEADDED, SDL_AUDIODEVICEREMOVED, SDL_CLIPBOARDUPDATE,
   SDL_CONTROLLERAXISMOTION, SDL_CONTROLLERBUTTONDOWN, SDL_CONTROLLERBUTTONUP,
   SDL_CONTROLLERDEVICEADDED, SDL_CONTROLLERDEVICEREMAPPED,
-  SDL_CONTROLLERDEVICEREMOVED, SDL_DROPBEGIN, SDL_DROPCOMPLETE, SDL_DROPFILE,
+  SDL_CONTROLLERDEVICEREMOVED, SDL_DISPLAYEVENT, SDL_DOLLARGESTURE,
+  SDL_DOLLARRECORD, SDL_DROPBEGIN, SDL_DROPCOMPLETE, SDL_DROPFILE,
   SDL_DROPTEXT, SDL_FINGERDOWN, SDL_FINGERMOTION, SDL_FINGERUP,
   SDL_JOYAXISMOTION, SDL_JOYBALLMOTION, SDL_JOYBUTTONDOWN, SDL_JOYBUTTONUP,
   SDL_JOYDEVICEADDED, SDL_JOYDEVICEREMOVED, SDL_JOYHATMOTION, SDL_KEYDOWN,
-  SDL_KEYUP, SDL_MOUSEBUTTONDOWN, SDL_MOUSEBUTTONUP, SDL_MOUSEMOTION,
-  SDL_MOUSEWHEEL, SDL_MULTIGESTURE, SDL_QUIT, SDL_WINDOWEVENT,
+  SDL_KEYUP, SDL_LASTEVENT, SDL_MOUSEBUTTONDOWN, SDL_MOUSEBUTTONUP,
+  SDL_MOUSEMOTION, SDL_MOUSEWHEEL, SDL_MULTIGESTURE, SDL_QUIT,
+  SDL_RENDER_DEVICE_RESET, SDL_RENDER_TARGETS_RESET, SDL_SENSORUPDATE,
+  SDL_SYSWMEVENT, SDL_TEXTEDITING, SDL_TEXTINPUT, SDL_USEREVENT,
+  SDL_WINDOWEVENT,
 };
 
 use crate::{
-  AudioDeviceID, FingerID, JoystickID, MouseButtonState, MouseID, TouchID,
-  WindowID,
+  AudioDeviceID, FingerID, GestureID, JoystickDeviceIndex, JoystickID,
+  KeyModifiers, Keycode, MouseButtonState, MouseID, Scancode, SensorID,
+  TouchID, UserEvent, WindowID,
 };
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 #[non_exhaustive]
 pub enum Event {
-  // TODO: DisplayEvent
+  Display(DisplayEvent),
   Window(WindowEvent),
+  SysWM(SysWMEvent),
   Keyboard(KeyboardEvent),
-  /* TODO: TextEditing,
-   * TODO: TextInput, */
+  TextEditing(TextEditingEvent),
+  TextInput(TextInputEvent),
+  /// The system clipboard changed.
+  ///
+  /// This has no data, so fetch the new clipboard contents yourself if you
+  /// need them.
+  ClipboardUpdate,
   MouseMotion(MouseMotionEvent),
   MouseButton(MouseButtonEvent),
   MouseWheel(MouseWheelEvent),
@@ -38,12 +52,59 @@ pub enum Event {
   ControllerAxis(ControllerAxisEvent),
   ControllerButton(ControllerButtonEvent),
   ControllerDevice(ControllerDeviceEvent),
+  // TODO: ControllerTouchpad and ControllerSensor, once we bind SDL 2.0.14
   AudioDevice(AudioDeviceEvent),
+  SensorUpdate(SensorUpdateEvent),
+  /// The whole program has been asked to quit.
+  ///
+  /// SDL sends this when the last window is closed, as well as for things
+  /// like Ctrl+C on the terminal or Cmd+Q on Mac. With more than one window
+  /// you'll also want to watch for [`WindowEvent::Close`].
   Quit,
-  TouchFinger(TouchFingerEvent),
+  /// The OS is ending the program.
+  ///
+  /// This and the other `App*` events come from iOS and Android (and
+  /// WinRT). Each must be dealt with before the OS moves on, so use an
+  /// [event filter](crate::Sdl::set_event_filter) to see them as they happen,
+  /// rather than when you get around to polling.
+  AppTerminating,
+  /// The OS is low on memory, so free what you can.
+  AppLowMemory,
+  /// The program is about to go into the background.
+  ///
+  /// Stop rendering and pause any audio or simulation.
+  AppWillEnterBackground,
+  /// The program is now in the background.
+  ///
+  /// Save your state here, since the OS may end the program at any time
+  /// without another event.
+  AppDidEnterBackground,
+  /// The program is about to come back to the foreground.
+  AppWillEnterForeground,
+  /// The program is in the foreground again, so start rendering again.
+  AppDidEnterForeground,
+  FingerDown(TouchFingerEvent),
+  FingerUp(TouchFingerEvent),
+  FingerMotion(TouchFingerEvent),
   MultiGesture(MultiGestureEvent),
-  // TODO: DollarGesture
+  DollarGesture(DollarGestureEvent),
+  DollarRecord(DollarRecordEvent),
   FileDrop(FileDropEvent),
+  /// The contents of all target textures were lost, and need to be drawn
+  /// again.
+  ///
+  /// The textures themselves are still fine to use. This happens with the
+  /// Direct3D renderer when the window is resized or goes fullscreen.
+  RenderTargetsReset,
+  /// The renderer's device was lost and made again, so the contents of all
+  /// textures were lost.
+  ///
+  /// The `Texture` values are still valid, but you must upload every
+  /// texture's pixels again (and redraw any target textures). This can happen
+  /// with the Direct3D renderer on Windows, eg: when a graphics driver
+  /// updates.
+  RenderDeviceReset,
+  User(UserEvent),
 }
 
 impl TryFrom<SDL_Event> for Event {
@@ -54,8 +115,15 @@ impl TryFrom<SDL_Event> for Event {
     // Safety: `sdl_event` is a union so there's all sorts of union access here
     unsafe {
       Ok(match sdl_event.type_ as SDL_EventType {
+        SDL_DISPLAYEVENT => Event::Display(sdl_event.display.try_into()?),
         SDL_WINDOWEVENT => Event::Window(sdl_event.window.try_into()?),
+        SDL_SYSWMEVENT => Event::SysWM(SysWMEvent::copy_from_sdl(
+          sdl_event.syswm.msg.as_ref().ok_or(())?,
+        )),
         SDL_KEYDOWN | SDL_KEYUP => Event::Keyboard(sdl_event.key.into()),
+        SDL_TEXTEDITING => Event::TextEditing(sdl_event.edit.into()),
+        SDL_TEXTINPUT => Event::TextInput(sdl_event.text.into()),
+        SDL_CLIPBOARDUPDATE => Event::ClipboardUpdate,
         SDL_MOUSEMOTION => Event::MouseMotion(sdl_event.motion.into()),
         SDL_MOUSEBUTTONDOWN | SDL_MOUSEBUTTONUP => {
           Event::MouseButton(sdl_event.button.into())
@@ -84,20 +152,270 @@ impl TryFrom<SDL_Event> for Event {
         SDL_AUDIODEVICEADDED | SDL_AUDIODEVICEREMOVED => {
           Event::AudioDevice(sdl_event.adevice.try_into()?)
         }
+        SDL_SENSORUPDATE => Event::SensorUpdate(sdl_event.sensor.into()),
         SDL_QUIT => Event::Quit,
-        SDL_FINGERMOTION | SDL_FINGERDOWN | SDL_FINGERUP => {
-          Event::TouchFinger(sdl_event.tfinger.try_into()?)
-        }
+        SDL_APP_TERMINATING => Event::AppTerminating,
+        SDL_APP_LOWMEMORY => Event::AppLowMemory,
+        SDL_APP_WILLENTERBACKGROUND => Event::AppWillEnterBackground,
+        SDL_APP_DIDENTERBACKGROUND => Event::AppDidEnterBackground,
+        SDL_APP_WILLENTERFOREGROUND => Event::AppWillEnterForeground,
+        SDL_APP_DIDENTERFOREGROUND => Event::AppDidEnterForeground,
+        SDL_FINGERDOWN => Event::FingerDown(sdl_event.tfinger.into()),
+        SDL_FINGERUP => Event::FingerUp(sdl_event.tfinger.into()),
+        SDL_FINGERMOTION => Event::FingerMotion(sdl_event.tfinger.into()),
         SDL_MULTIGESTURE => Event::MultiGesture(sdl_event.mgesture.into()),
+        SDL_DOLLARGESTURE => Event::DollarGesture(sdl_event.dgesture.into()),
+        SDL_DOLLARRECORD => Event::DollarRecord(sdl_event.dgesture.into()),
         SDL_DROPFILE | SDL_DROPTEXT | SDL_DROPBEGIN | SDL_DROPCOMPLETE => {
           Event::FileDrop(sdl_event.drop.try_into()?)
         }
+        SDL_RENDER_TARGETS_RESET => Event::RenderTargetsReset,
+        SDL_RENDER_DEVICE_RESET => Event::RenderDeviceReset,
+        t if (SDL_USEREVENT..SDL_LASTEVENT).contains(&t) => {
+          Event::User(UserEvent::take_from_sdl(&sdl_event.user).ok_or(())?)
+        }
+        _ => return Err(()),
+      })
+    }
+  }
+}
+
+impl Event {
+  /// If this is the whole program being asked to quit.
+  pub fn is_quit(&self) -> bool {
+    matches!(self, Event::Quit)
+  }
+
+  /// The window that the event is for, if it's for one.
+  ///
+  /// Use this to send input to the right place when there's more than one
+  /// window. Compare it against [`Window::id`](crate::Window::id).
+  pub fn window_id(&self) -> Option<WindowID> {
+    Some(match self {
+      Event::Window(e) => e.window_id(),
+      Event::Keyboard(e) => e.window_id,
+      Event::TextEditing(e) => e.window_id,
+      Event::TextInput(e) => e.window_id,
+      Event::MouseMotion(e) => e.window_id,
+      Event::MouseButton(e) => e.window_id,
+      Event::MouseWheel(e) => e.window_id,
+      Event::FingerDown(e) | Event::FingerUp(e) | Event::FingerMotion(e) => {
+        e.window_id
+      }
+      Event::FileDrop(e) => e.window_id(),
+      _ => return None,
+    })
+  }
+
+  /// If this is a window being asked to close, this gives which one.
+  ///
+  /// Closing one window doesn't end the program, so it's up to you to close
+  /// (drop) the window.
+  pub fn close_requested(&self) -> Option<WindowID> {
+    match self {
+      Event::Window(WindowEvent::Close { window_id }) => Some(*window_id),
+      _ => None,
+    }
+  }
+
+  /// Parses an event that's still owned by SDL, such as one that's being
+  /// looked at in an event filter.
+  ///
+  /// Unlike `try_from`, this copies drop strings and clones user payloads
+  /// rather than taking them, so SDL's copy of the event stays valid.
+  ///
+  /// ## Safety
+  /// * Any user event payload must still be alive.
+  pub(crate) unsafe fn peek_from_sdl(
+    sdl_event: &SDL_Event,
+  ) -> Result<Self, ()> {
+    Ok(match sdl_event.type_ as SDL_EventType {
+      SDL_DROPFILE | SDL_DROPTEXT | SDL_DROPBEGIN | SDL_DROPCOMPLETE => {
+        Event::FileDrop(FileDropEvent::peek_from_sdl(&sdl_event.drop)?)
+      }
+      t if (SDL_USEREVENT..SDL_LASTEVENT).contains(&t) => {
+        Event::User(UserEvent::clone_from_sdl(&sdl_event.user).ok_or(())?)
+      }
+      _ => Event::try_from(*sdl_event)?,
+    })
+  }
+}
+
+pub use display_event::*;
+mod display_event {
+  use super::*;
+  use fermium::{
+    SDL_DisplayEvent, SDL_DisplayEventID, SDL_DisplayOrientation,
+    SDL_DISPLAYEVENT_ORIENTATION, SDL_ORIENTATION_LANDSCAPE,
+    SDL_ORIENTATION_LANDSCAPE_FLIPPED, SDL_ORIENTATION_PORTRAIT,
+    SDL_ORIENTATION_PORTRAIT_FLIPPED,
+  };
+
+  // Note: these are from SDL 2.0.14, so fermium doesn't have them yet.
+  const SDL_DISPLAYEVENT_CONNECTED: SDL_DisplayEventID = 2;
+  const SDL_DISPLAYEVENT_DISCONNECTED: SDL_DisplayEventID = 3;
+
+  /// Which way up a display is.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub enum DisplayOrientation {
+    /// The orientation can't be found out (eg: on most desktops).
+    Unknown,
+    /// Wider than it is tall, right side up.
+    Landscape,
+    /// Wider than it is tall, upside down.
+    LandscapeFlipped,
+    /// Taller than it is wide, right side up.
+    Portrait,
+    /// Taller than it is wide, upside down.
+    PortraitFlipped,
+  }
+  impl From<SDL_DisplayOrientation> for DisplayOrientation {
+    #[inline]
+    fn from(orientation: SDL_DisplayOrientation) -> Self {
+      match orientation {
+        SDL_ORIENTATION_LANDSCAPE => Self::Landscape,
+        SDL_ORIENTATION_LANDSCAPE_FLIPPED => Self::LandscapeFlipped,
+        SDL_ORIENTATION_PORTRAIT => Self::Portrait,
+        SDL_ORIENTATION_PORTRAIT_FLIPPED => Self::PortraitFlipped,
+        _ => Self::Unknown,
+      }
+    }
+  }
+
+  /// Something happened to a display (a monitor, or a device's screen).
+  ///
+  /// `display_index` is the same index that SDL's display functions use.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub enum DisplayEvent {
+    /// The display was rotated.
+    Orientation { display_index: u32, orientation: DisplayOrientation },
+    /// A display was plugged in. Needs SDL 2.0.14 or later at runtime.
+    Connected { display_index: u32 },
+    /// A display was unplugged. Needs SDL 2.0.14 or later at runtime.
+    ///
+    /// Windows on that display are moved elsewhere by the OS, so check their
+    /// positions and sizes again.
+    Disconnected { display_index: u32 },
+  }
+  impl DisplayEvent {
+    /// The display that the event is for.
+    pub fn display_index(&self) -> u32 {
+      match *self {
+        Self::Orientation { display_index, .. }
+        | Self::Connected { display_index }
+        | Self::Disconnected { display_index } => display_index,
+      }
+    }
+  }
+  impl TryFrom<SDL_DisplayEvent> for DisplayEvent {
+    type Error = ();
+    #[inline]
+    fn try_from(display_event: SDL_DisplayEvent) -> Result<Self, Self::Error> {
+      let display_index = display_event.display;
+      Ok(match display_event.event as SDL_DisplayEventID {
+        SDL_DISPLAYEVENT_ORIENTATION => Self::Orientation {
+          display_index,
+          orientation: DisplayOrientation::from(
+            display_event.data1 as SDL_DisplayOrientation,
+          ),
+        },
+        SDL_DISPLAYEVENT_CONNECTED => Self::Connected { display_index },
+        SDL_DISPLAYEVENT_DISCONNECTED => Self::Disconnected { display_index },
         _ => return Err(()),
       })
     }
   }
 }
 
+pub use syswm_event::*;
+mod syswm_event {
+  use super::*;
+  use core::cmp::Ordering;
+  use fermium::SDL_SysWMmsg;
+
+  /// A raw message from the OS's window system, for things SDL doesn't
+  /// handle.
+  ///
+  /// SDL doesn't send these unless you turn them on with
+  /// `sdl.set_event_enabled(EventKind::SYS_WM, true)`.
+  ///
+  /// The message is copied out of SDL, so it stays valid after the next poll.
+  ///
+  /// These are never equal to each other (not even to themselves), since
+  /// there's no sound way to compare the platform data inside.
+  #[derive(Clone, Copy)]
+  pub struct SysWMEvent {
+    msg: SDL_SysWMmsg,
+  }
+  impl SysWMEvent {
+    pub(crate) fn copy_from_sdl(msg: &SDL_SysWMmsg) -> Self {
+      Self { msg: *msg }
+    }
+
+    /// Which window system the message is from (an `SDL_SYSWM_TYPE`).
+    // Note: `SDL_SYSWM_TYPE` is `i32` on some targets.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn subsystem(&self) -> u32 {
+      self.msg.subsystem as u32
+    }
+
+    /// The message just as SDL gave it.
+    pub fn as_raw(&self) -> &SDL_SysWMmsg {
+      &self.msg
+    }
+
+    /// The message, if it came from Windows.
+    #[cfg(windows)]
+    pub fn windows_message(&self) -> Option<WindowsMessage> {
+      if self.msg.subsystem != fermium::SDL_SYSWM_WINDOWS {
+        return None;
+      }
+      let win = unsafe { self.msg.msg.win };
+      Some(WindowsMessage {
+        hwnd: win.hwnd.cast(),
+        msg: win.msg as u32,
+        wparam: win.wParam as usize,
+        lparam: win.lParam as isize,
+      })
+    }
+
+    /// The `XEvent`, if the message came from X11.
+    #[cfg(target_os = "linux")]
+    pub fn x11_event(&self) -> Option<&fermium::XEvent> {
+      if self.msg.subsystem == fermium::SDL_SYSWM_X11 {
+        Some(unsafe { &self.msg.msg.x11.event })
+      } else {
+        None
+      }
+    }
+  }
+  impl core::fmt::Debug for SysWMEvent {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+      write!(f, "SysWMEvent {{ subsystem: {} }}", self.subsystem())
+    }
+  }
+  impl PartialEq for SysWMEvent {
+    fn eq(&self, _other: &Self) -> bool {
+      false
+    }
+  }
+  impl PartialOrd for SysWMEvent {
+    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
+      None
+    }
+  }
+
+  /// A Windows message, as a window procedure would get it.
+  #[cfg(windows)]
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub struct WindowsMessage {
+    pub hwnd: *mut core::ffi::c_void,
+    pub msg: u32,
+    pub wparam: usize,
+    pub lparam: isize,
+  }
+}
+
 pub use window_event::*;
 mod window_event {
   use super::*;
@@ -112,26 +430,72 @@ mod window_event {
     SDL_WINDOWEVENT_TAKE_FOCUS,
   };
 
+  /// Something happened to one of the windows.
+  ///
+  /// Every variant has the ID of the window it's about, compare it against
+  /// [`Window::id`](crate::Window::id).
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   #[non_exhaustive]
   pub enum WindowEvent {
+    /// The window was shown.
     Shown { window_id: WindowID },
+    /// The window was hidden.
     Hidden { window_id: WindowID },
+    /// Part of the window was uncovered and it should be redrawn.
     Exposed { window_id: WindowID },
+    /// The window was maximized.
     Maximized { window_id: WindowID },
+    /// The window was minimized.
     Minimized { window_id: WindowID },
+    /// The window went back to its normal size and position.
     Restored { window_id: WindowID },
+    /// The mouse moved into the window.
     MouseEntered { window_id: WindowID },
+    /// The mouse moved out of the window.
     MouseLeft { window_id: WindowID },
+    /// The window gained keyboard focus.
     FocusGained { window_id: WindowID },
+    /// The window lost keyboard focus.
     FocusLost { window_id: WindowID },
+    /// The window manager wants the window closed.
     Close { window_id: WindowID },
+    /// The window is being offered focus, and should set focus on itself or
+    /// one of its subwindows.
     TakeFocus { window_id: WindowID },
+    /// The window had a hit test that wasn't `SDL_HITTEST_NORMAL`.
     HitTest { window_id: WindowID },
+    /// The window moved to the given position.
     Moved { window_id: WindowID, x: i32, y: i32 },
+    /// The window was resized by the user or window manager. This is always
+    /// followed by a `SizeChanged`.
     Resized { window_id: WindowID, width: u32, height: u32 },
+    /// The window size changed for any reason, including a call to set the
+    /// size from the program.
     SizeChanged { window_id: WindowID, width: u32, height: u32 },
   }
+  impl WindowEvent {
+    /// The window that the event is for.
+    pub fn window_id(&self) -> WindowID {
+      match *self {
+        Self::Shown { window_id }
+        | Self::Hidden { window_id }
+        | Self::Exposed { window_id }
+        | Self::Maximized { window_id }
+        | Self::Minimized { window_id }
+        | Self::Restored { window_id }
+        | Self::MouseEntered { window_id }
+        | Self::MouseLeft { window_id }
+        | Self::FocusGained { window_id }
+        | Self::FocusLost { window_id }
+        | Self::Close { window_id }
+        | Self::TakeFocus { window_id }
+        | Self::HitTest { window_id }
+        | Self::Moved { window_id, .. }
+        | Self::Resized { window_id, .. }
+        | Self::SizeChanged { window_id, .. } => window_id,
+      }
+    }
+  }
 
   impl TryFrom<SDL_WindowEvent> for WindowEvent {
     type Error = ();
@@ -177,20 +541,15 @@ mod window_event {
 pub use keyboard_event::*;
 mod keyboard_event {
   use super::*;
-  use fermium::{SDL_KeyboardEvent, SDL_Keysym, SDL_PRESSED};
-  //
-  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-  #[repr(transparent)]
-  pub struct Scancode(u32);
-  //
-  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-  #[repr(transparent)]
-  pub struct Keycode(u32);
-  //
-  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-  #[repr(transparent)]
-  pub struct KeyModifiers(u16);
-  //
+  use fermium::{SDL_KeyboardEvent, SDL_PRESSED};
+
+  /// A key was pressed or released.
+  ///
+  /// * `scancode` is the physical key, `keycode` is what the key means in the
+  ///   current layout.
+  /// * `modifiers` is the state of all the modifier keys as of this event.
+  /// * `is_repeat` is set for the extra key downs that the OS sends while a
+  ///   key is held. Don't use these for text, use `TextInput` events.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub struct KeyboardEvent {
     pub window_id: WindowID,
@@ -198,23 +557,83 @@ mod keyboard_event {
     pub keycode: Keycode,
     pub modifiers: KeyModifiers,
     pub is_pressed: bool,
-    pub repeat: u8,
+    pub is_repeat: bool,
   }
   impl From<SDL_KeyboardEvent> for KeyboardEvent {
     #[inline]
     #[must_use]
+    // Note: `SDL_Scancode` is `i32` on some targets.
+    #[allow(clippy::unnecessary_cast)]
     fn from(keyboard_event: SDL_KeyboardEvent) -> Self {
       Self {
         window_id: WindowID(keyboard_event.windowID),
-        scancode: Scancode(keyboard_event.keysym.scancode as u32),
-        keycode: Keycode(keyboard_event.keysym.sym as u32),
+        scancode: Scancode::from_raw(keyboard_event.keysym.scancode as u32),
+        keycode: Keycode::from_raw(keyboard_event.keysym.sym),
         modifiers: KeyModifiers(keyboard_event.keysym.mod_),
         is_pressed: keyboard_event.state as u32 == SDL_PRESSED,
-        repeat: keyboard_event.repeat,
+        is_repeat: keyboard_event.repeat != 0,
+      }
+    }
+  }
+}
+
+pub use text_events::*;
+mod text_events {
+  use super::*;
+  use fermium::{SDL_TextEditingEvent, SDL_TextInputEvent};
+
+  /// Text that the user entered, after any IME composition has finished.
+  ///
+  /// Use this rather than keyboard events for text fields, since one key press
+  /// can make several characters (or none). Long input can be split over more
+  /// than one event.
+  #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub struct TextInputEvent {
+    pub window_id: WindowID,
+    pub text: String,
+  }
+  impl From<SDL_TextInputEvent> for TextInputEvent {
+    #[inline]
+    fn from(text_input_event: SDL_TextInputEvent) -> Self {
+      Self {
+        window_id: WindowID(text_input_event.windowID),
+        text: text_from_buffer(&text_input_event.text),
+      }
+    }
+  }
+
+  /// The in-progress text of an IME composition.
+  ///
+  /// * `start` is where the edit begins within the composition, in characters.
+  /// * `length` is how many characters are selected.
+  #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub struct TextEditingEvent {
+    pub window_id: WindowID,
+    pub text: String,
+    pub start: i32,
+    pub length: i32,
+  }
+  impl From<SDL_TextEditingEvent> for TextEditingEvent {
+    #[inline]
+    fn from(text_editing_event: SDL_TextEditingEvent) -> Self {
+      Self {
+        window_id: WindowID(text_editing_event.windowID),
+        text: text_from_buffer(&text_editing_event.text),
+        start: text_editing_event.start,
+        length: text_editing_event.length,
       }
     }
   }
-  // TODO: Key constants
+
+  /// Reads the null terminated UTF-8 in one of SDL's fixed size text buffers.
+  fn text_from_buffer(buf: &[fermium::c_char]) -> String {
+    let bytes: Vec<u8> =
+      buf.iter().map(|&c| c as u8).take_while(|&b| b != 0).collect();
+    match String::from_utf8(bytes) {
+      Ok(s) => s,
+      Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    }
+  }
 }
 
 pub use mouse_motion::*;
@@ -254,6 +673,12 @@ mod mouse_button {
   use super::*;
   use fermium::{SDL_MouseButtonEvent, SDL_PRESSED};
   //
+  /// A mouse button was pressed or released.
+  ///
+  /// * `button` is the button number: 1 is left, 2 is middle, 3 is right.
+  /// * `clicks` is how many clicks in a row this makes (2 for a double
+  ///   click), using the OS's double click timing. Use a
+  ///   [`ClickCounter`](crate::ClickCounter) for your own timing.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub struct MouseButtonEvent {
     pub window_id: WindowID,
@@ -286,12 +711,22 @@ mod mouse_wheel {
   use super::*;
   use fermium::{SDL_MouseWheelEvent, SDL_MOUSEWHEEL_FLIPPED};
   //
+  /// The mouse wheel (or a trackpad) scrolled.
+  ///
+  /// * Positive `dx` is to the right, positive `dy` is away from the user.
+  /// * When the OS reports the wheel as flipped ("natural" scrolling) the deltas
+  ///   are negated back, so they always match the physical motion, and
+  ///   `is_flipped` is set. Negate them again if you want to follow the OS
+  ///   setting.
+  /// * SDL 2.0.12 only reports whole wheel steps. The fractional `preciseX`
+  ///   and `preciseY` values are from SDL 2.0.18.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub struct MouseWheelEvent {
     pub window_id: WindowID,
     pub mouse_id: MouseID,
     pub dx: i32,
     pub dy: i32,
+    pub is_flipped: bool,
   }
   impl From<SDL_MouseWheelEvent> for MouseWheelEvent {
     #[inline]
@@ -302,8 +737,10 @@ mod mouse_wheel {
         mouse_id: MouseID(mouse_wheel_event.which),
         dx: mouse_wheel_event.x,
         dy: mouse_wheel_event.y,
+        is_flipped: mouse_wheel_event.direction
+          == SDL_MOUSEWHEEL_FLIPPED as u32,
       };
-      if mouse_wheel_event.direction == SDL_MOUSEWHEEL_FLIPPED as u32 {
+      if out.is_flipped {
         out.dx = -out.dx;
         out.dy = -out.dy;
       }
@@ -452,10 +889,15 @@ mod joy_device {
   use super::*;
   use fermium::SDL_JoyDeviceEvent;
 
+  /// A joystick was plugged in or unplugged.
+  ///
+  /// * `Added` has the device index to open the new joystick with. You also get
+  ///   one of these for each joystick already attached when SDL starts.
+  /// * `Removed` has the instance ID of the joystick that went away.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   #[non_exhaustive]
   pub enum JoyDeviceEvent {
-    Added { device_index: i32 },
+    Added { device_index: JoystickDeviceIndex },
     Removed { joystick_id: JoystickID },
   }
 
@@ -467,9 +909,9 @@ mod joy_device {
       joy_device_event: SDL_JoyDeviceEvent,
     ) -> Result<Self, Self::Error> {
       Ok(match joy_device_event.type_ as SDL_EventType {
-        SDL_JOYDEVICEADDED => {
-          Self::Added { device_index: joy_device_event.which }
-        }
+        SDL_JOYDEVICEADDED => Self::Added {
+          device_index: JoystickDeviceIndex(joy_device_event.which as usize),
+        },
         SDL_JOYDEVICEREMOVED => {
           Self::Removed { joystick_id: JoystickID(joy_device_event.which) }
         }
@@ -490,6 +932,7 @@ mod controller_axis {
   };
 
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
   pub enum ControllerAxis {
     Invalid = SDL_CONTROLLER_AXIS_INVALID as _,
     LeftX = SDL_CONTROLLER_AXIS_LEFTX as _,
@@ -514,12 +957,43 @@ mod controller_axis {
       }
     }
   }
+  impl ControllerAxis {
+    /// The name SDL uses for this axis, as in mapping strings (eg: "leftx").
+    ///
+    /// `Invalid` has no name.
+    pub fn name(self) -> Option<String> {
+      let p = unsafe { fermium::SDL_GameControllerGetStringForAxis(self as _) };
+      if p.is_null() {
+        None
+      } else {
+        Some(unsafe { crate::string_from_sdl(p.cast()) })
+      }
+    }
+
+    /// Looks up an axis by the name that [`name`](ControllerAxis::name)
+    /// gives.
+    pub fn from_name(name: &str) -> Option<Self> {
+      let name_null: Vec<u8> =
+        name.as_bytes().iter().copied().chain(Some(0)).collect();
+      match Self::from(unsafe {
+        fermium::SDL_GameControllerGetAxisFromString(name_null.as_ptr().cast())
+      } as u8)
+      {
+        Self::Invalid => None,
+        axis => Some(axis),
+      }
+    }
+  }
 
+  /// A controller axis moved.
+  ///
+  /// Sticks range over the full `i16` range, with positive being right or
+  /// down. Triggers range from 0 to `i16::MAX`.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub struct ControllerAxisEvent {
-    joystick_id: JoystickID,
-    axis: ControllerAxis,
-    value: i16,
+    pub joystick_id: JoystickID,
+    pub axis: ControllerAxis,
+    pub value: i16,
   }
 
   impl From<SDL_ControllerAxisEvent> for ControllerAxisEvent {
@@ -551,6 +1025,7 @@ mod controller_button {
   };
 
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
   pub enum ControllerButton {
     Invalid = SDL_CONTROLLER_BUTTON_INVALID as _,
     North = SDL_CONTROLLER_BUTTON_Y as _,
@@ -593,12 +1068,47 @@ mod controller_button {
       }
     }
   }
+  impl ControllerButton {
+    /// The name SDL uses for this button, as in mapping strings (eg: "a" for
+    /// `South`, or "dpup" for `Up`).
+    ///
+    /// `Invalid` has no name.
+    pub fn name(self) -> Option<String> {
+      let p =
+        unsafe { fermium::SDL_GameControllerGetStringForButton(self as _) };
+      if p.is_null() {
+        None
+      } else {
+        Some(unsafe { crate::string_from_sdl(p.cast()) })
+      }
+    }
+
+    /// Looks up a button by the name that [`name`](ControllerButton::name)
+    /// gives.
+    pub fn from_name(name: &str) -> Option<Self> {
+      let name_null: Vec<u8> =
+        name.as_bytes().iter().copied().chain(Some(0)).collect();
+      match Self::from(unsafe {
+        fermium::SDL_GameControllerGetButtonFromString(
+          name_null.as_ptr().cast(),
+        )
+      } as u8)
+      {
+        Self::Invalid => None,
+        button => Some(button),
+      }
+    }
+  }
 
+  /// A controller button was pressed or released.
+  ///
+  /// The face buttons are named by position (`North`, `South`, and so on)
+  /// rather than by label, since the labels differ between brands.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub struct ControllerButtonEvent {
-    joystick_id: JoystickID,
-    button: ControllerButton,
-    is_pressed: bool,
+    pub joystick_id: JoystickID,
+    pub button: ControllerButton,
+    pub is_pressed: bool,
   }
 
   impl From<SDL_ControllerButtonEvent> for ControllerButtonEvent {
@@ -619,10 +1129,17 @@ mod controller_device {
   use super::*;
   use fermium::SDL_ControllerDeviceEvent;
 
+  /// A controller was plugged in, unplugged, or had its mapping changed.
+  ///
+  /// * `Added` has the device index to pass to
+  ///   [`Sdl::open_controller`](crate::Sdl::open_controller). You also get one
+  ///   of these for each controller already attached when SDL starts.
+  /// * `Removed` and `Remapped` have the instance ID of the controller, compare
+  ///   it against [`Controller::joystick_id`](crate::Controller::joystick_id).
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   #[non_exhaustive]
   pub enum ControllerDeviceEvent {
-    Added { device_index: i32 },
+    Added { device_index: JoystickDeviceIndex },
     Removed { joystick_id: JoystickID },
     Remapped { joystick_id: JoystickID },
   }
@@ -635,9 +1152,11 @@ mod controller_device {
       controller_device_event: SDL_ControllerDeviceEvent,
     ) -> Result<Self, Self::Error> {
       Ok(match controller_device_event.type_ as SDL_EventType {
-        SDL_CONTROLLERDEVICEADDED => {
-          Self::Added { device_index: controller_device_event.which }
-        }
+        SDL_CONTROLLERDEVICEADDED => Self::Added {
+          device_index: JoystickDeviceIndex(
+            controller_device_event.which as usize,
+          ),
+        },
         SDL_CONTROLLERDEVICEREMOVED => Self::Removed {
           joystick_id: JoystickID(controller_device_event.which),
         },
@@ -655,6 +1174,13 @@ mod audio_device {
   use super::*;
   use fermium::SDL_AudioDeviceEvent;
 
+  /// An audio device was plugged in or unplugged.
+  ///
+  /// * `Added` has the index of the new device in SDL's device list. You also
+  ///   get one of these for each device already attached when SDL starts.
+  /// * `Removed` is only sent for devices that you have open, and has the ID
+  ///   of the open device. Compare it against the `device_id` of your audio
+  ///   device. The device stays open (playing silence) until you close it.
   #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
   #[non_exhaustive]
   pub enum AudioDeviceEvent {
@@ -670,11 +1196,11 @@ mod audio_device {
       audio_device_event: SDL_AudioDeviceEvent,
     ) -> Result<Self, Self::Error> {
       Ok(match audio_device_event.type_ as SDL_EventType {
-        SDL_CONTROLLERDEVICEADDED => Self::Added {
+        SDL_AUDIODEVICEADDED => Self::Added {
           device_index: audio_device_event.which,
           is_capture: audio_device_event.iscapture != 0,
         },
-        SDL_CONTROLLERDEVICEREMOVED => Self::Removed {
+        SDL_AUDIODEVICEREMOVED => Self::Removed {
           device_id: AudioDeviceID(audio_device_event.which),
           is_capture: audio_device_event.iscapture != 0,
         },
@@ -684,45 +1210,58 @@ mod audio_device {
   }
 }
 
+pub use sensor_update::*;
+mod sensor_update {
+  use super::*;
+  use fermium::SDL_SensorEvent;
+
+  /// New readings from a sensor (eg: an accelerometer or gyroscope).
+  ///
+  /// How many of the `data` values are used, and what they mean, depends on
+  /// the type of sensor. See `SDL_sensor.h` for the details.
+  #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+  pub struct SensorUpdateEvent {
+    pub sensor_id: SensorID,
+    pub data: [f32; 6],
+  }
+
+  impl From<SDL_SensorEvent> for SensorUpdateEvent {
+    #[inline]
+    fn from(sensor_event: SDL_SensorEvent) -> Self {
+      Self { sensor_id: SensorID(sensor_event.which), data: sensor_event.data }
+    }
+  }
+}
+
 pub use touch_finger::*;
 mod touch_finger {
   use super::*;
   use fermium::SDL_TouchFingerEvent;
 
-  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-  #[non_exhaustive]
-  pub enum TouchFingerEventType {
-    Motion,
-    Down,
-    Up,
-  }
-
+  /// A finger touched, left, or moved on a touch device.
+  ///
+  /// * Positions and deltas are normalized to `0.0 ..= 1.0` across the device.
+  /// * `pressure` is also normalized to `0.0 ..= 1.0`.
+  /// * The `finger_id` stays the same from when a finger goes down until it's
+  ///   lifted, so it can be used to track multiple touches.
   #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
   pub struct TouchFingerEvent {
-    ty: TouchFingerEventType,
-    touch_id: TouchID,
-    finger_id: FingerID,
-    x: f32,
-    y: f32,
-    dx: f32,
-    dy: f32,
-    pressure: f32,
+    /// The window under the finger, if any (zero-valued otherwise).
+    pub window_id: WindowID,
+    pub touch_id: TouchID,
+    pub finger_id: FingerID,
+    pub x: f32,
+    pub y: f32,
+    pub dx: f32,
+    pub dy: f32,
+    pub pressure: f32,
   }
 
-  impl TryFrom<SDL_TouchFingerEvent> for TouchFingerEvent {
-    type Error = ();
+  impl From<SDL_TouchFingerEvent> for TouchFingerEvent {
     #[inline]
-    #[must_use]
-    fn try_from(
-      touch_finger_event: SDL_TouchFingerEvent,
-    ) -> Result<Self, Self::Error> {
-      Ok(Self {
-        ty: match touch_finger_event.type_ as SDL_EventType {
-          SDL_FINGERMOTION => TouchFingerEventType::Motion,
-          SDL_FINGERDOWN => TouchFingerEventType::Down,
-          SDL_FINGERUP => TouchFingerEventType::Up,
-          _ => return Err(()),
-        },
+    fn from(touch_finger_event: SDL_TouchFingerEvent) -> Self {
+      Self {
+        window_id: WindowID(touch_finger_event.windowID),
         touch_id: TouchID(touch_finger_event.touchId),
         finger_id: FingerID(touch_finger_event.fingerId),
         x: touch_finger_event.x,
@@ -730,7 +1269,7 @@ mod touch_finger {
         dx: touch_finger_event.dx,
         dy: touch_finger_event.dy,
         pressure: touch_finger_event.pressure,
-      })
+      }
     }
   }
 }
@@ -740,14 +1279,20 @@ mod multi_gesture {
   use super::*;
   use fermium::SDL_MultiGestureEvent;
 
+  /// Two or more fingers moved together, such as for a pinch or a twist.
+  ///
+  /// * `d_angle` is how much the fingers rotated, in radians.
+  /// * `d_pinch` is how much the fingers moved apart (positive) or together
+  ///   (negative), normalized to the device size.
+  /// * The position is the normalized center of the fingers.
   #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
   pub struct MultiGestureEvent {
-    touch_id: TouchID,
-    d_angle: f32,
-    d_pinch: f32,
-    x_pos: f32,
-    y_pos: f32,
-    num_fingers: u16,
+    pub touch_id: TouchID,
+    pub d_angle: f32,
+    pub d_pinch: f32,
+    pub x_pos: f32,
+    pub y_pos: f32,
+    pub num_fingers: u16,
   }
 
   impl From<SDL_MultiGestureEvent> for MultiGestureEvent {
@@ -766,17 +1311,107 @@ mod multi_gesture {
   }
 }
 
+pub use dollar_gesture::*;
+mod dollar_gesture {
+  use super::*;
+  use fermium::SDL_DollarGestureEvent;
+
+  /// A touch matched one of the recorded "$1" gesture templates.
+  ///
+  /// * `error` is how far off the match was, lower is better.
+  /// * The position is the normalized center of the gesture.
+  #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+  pub struct DollarGestureEvent {
+    pub touch_id: TouchID,
+    pub gesture_id: GestureID,
+    pub num_fingers: u32,
+    pub error: f32,
+    pub x_pos: f32,
+    pub y_pos: f32,
+  }
+
+  impl From<SDL_DollarGestureEvent> for DollarGestureEvent {
+    #[inline]
+    fn from(dollar_gesture_event: SDL_DollarGestureEvent) -> Self {
+      Self {
+        touch_id: TouchID(dollar_gesture_event.touchId),
+        gesture_id: GestureID(dollar_gesture_event.gestureId),
+        num_fingers: dollar_gesture_event.numFingers,
+        error: dollar_gesture_event.error,
+        x_pos: dollar_gesture_event.x,
+        y_pos: dollar_gesture_event.y,
+      }
+    }
+  }
+
+  /// A new gesture template finished recording.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+  pub struct DollarRecordEvent {
+    pub touch_id: TouchID,
+    pub gesture_id: GestureID,
+  }
+
+  impl From<SDL_DollarGestureEvent> for DollarRecordEvent {
+    #[inline]
+    fn from(dollar_gesture_event: SDL_DollarGestureEvent) -> Self {
+      Self {
+        touch_id: TouchID(dollar_gesture_event.touchId),
+        gesture_id: GestureID(dollar_gesture_event.gestureId),
+      }
+    }
+  }
+}
+
 pub use file_drop::*;
 mod file_drop {
   use super::*;
   use fermium::SDL_DropEvent;
 
+  /// Something was dragged and dropped onto a window.
+  ///
+  /// Dropping several files at once gives a `Begin`, then a `File` for each
+  /// one, then a `Complete`.
   #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
   pub enum FileDropEvent {
-    File { window_id: WindowID, name: Vec<u8> },
-    Text { window_id: WindowID, text: Vec<u8> },
-    Begin,
-    Complete,
+    /// A file was dropped. The path is UTF-8, as SDL gives it.
+    File {
+      window_id: WindowID,
+      path: String,
+    },
+    /// Some text was dropped.
+    Text {
+      window_id: WindowID,
+      text: String,
+    },
+    Begin {
+      window_id: WindowID,
+    },
+    Complete {
+      window_id: WindowID,
+    },
+  }
+  impl FileDropEvent {
+    /// The window that things were dropped on.
+    ///
+    /// This is zero-valued if the drop wasn't on any particular window (eg:
+    /// files dropped on the dock icon on Mac).
+    pub fn window_id(&self) -> WindowID {
+      match *self {
+        Self::File { window_id, .. }
+        | Self::Text { window_id, .. }
+        | Self::Begin { window_id }
+        | Self::Complete { window_id } => window_id,
+      }
+    }
+
+    /// The dropped file's path, if this is a `File` event.
+    #[cfg(feature = "std")]
+    pub fn path_buf(&self) -> Option<std::path::PathBuf> {
+      match self {
+        Self::File { path, .. } => Some(std::path::PathBuf::from(path)),
+        _ => None,
+      }
+    }
   }
 
   impl TryFrom<SDL_DropEvent> for FileDropEvent {
@@ -784,37 +1419,51 @@ mod file_drop {
     #[inline]
     #[must_use]
     fn try_from(drop_event: SDL_DropEvent) -> Result<Self, Self::Error> {
+      // Safety: the event came out of the queue, so the string is ours now.
+      let out = unsafe { Self::peek_from_sdl(&drop_event) };
+      if !drop_event.file.is_null() {
+        unsafe { fermium::SDL_free(drop_event.file.cast()) };
+      }
+      out
+    }
+  }
+  impl FileDropEvent {
+    /// Parses the event, copying the string but leaving SDL's allocation
+    /// alone.
+    pub(crate) unsafe fn peek_from_sdl(
+      drop_event: &SDL_DropEvent,
+    ) -> Result<Self, ()> {
+      let window_id = WindowID(drop_event.windowID);
       Ok(match drop_event.type_ as SDL_EventType {
-        SDL_DROPFILE => unsafe {
-          let mut name = Vec::with_capacity(1024);
-          let mut ptr = drop_event.file as *const u8;
-          while *ptr != 0 {
-            name.push(*ptr);
-            ptr = ptr.add(1);
-          }
-          let out =
-            Self::File { window_id: WindowID(drop_event.windowID), name };
-          fermium::SDL_free(drop_event.file.cast());
-          out
-        },
-        SDL_DROPTEXT => unsafe {
-          let mut text = Vec::with_capacity(1024);
-          let mut ptr = drop_event.file as *const u8;
-          while *ptr != 0 {
-            text.push(*ptr);
-            ptr = ptr.add(1);
-          }
-          let out =
-            Self::Text { window_id: WindowID(drop_event.windowID), text };
-          fermium::SDL_free(drop_event.file.cast());
-          out
-        },
-        SDL_DROPBEGIN => Self::Begin,
-        SDL_DROPCOMPLETE => Self::Complete,
+        SDL_DROPFILE => {
+          Self::File { window_id, path: copy_sdl_string(drop_event) }
+        }
+        SDL_DROPTEXT => {
+          Self::Text { window_id, text: copy_sdl_string(drop_event) }
+        }
+        SDL_DROPBEGIN => Self::Begin { window_id },
+        SDL_DROPCOMPLETE => Self::Complete { window_id },
         _ => return Err(()),
       })
     }
   }
+
+  /// Copies the event's string out.
+  unsafe fn copy_sdl_string(drop_event: &SDL_DropEvent) -> String {
+    if drop_event.file.is_null() {
+      return String::new();
+    }
+    let mut bytes = Vec::with_capacity(1024);
+    let mut ptr = drop_event.file as *const u8;
+    while *ptr != 0 {
+      bytes.push(*ptr);
+      ptr = ptr.add(1);
+    }
+    match String::from_utf8(bytes) {
+      Ok(s) => s,
+      Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    }
+  }
 }
 
 //
@@ -0,0 +1,140 @@
+use core::{
+  ffi::c_void,
+  ptr::NonNull,
+  sync::atomic::{AtomicPtr, Ordering},
+};
+
+use alloc::{boxed::Box, sync::Arc};
+
+use fermium::SDL_Event;
+
+use crate::{Event, Initialization};
+
+pub(crate) type FilterFn = dyn FnMut(&Event) -> bool + Send + 'static;
+
+/// The closure behind the user data pointer we give to SDL.
+///
+/// It's boxed twice so that the pointer is thin.
+pub(crate) type FilterBox = Box<Box<FilterFn>>;
+
+/// Calls `f`, but doesn't let a panic unwind into SDL's C code.
+///
+/// * With `std`, the panic is caught and you get `default`.
+/// * Without `std`, the panic becomes an abort.
+pub(crate) fn call_without_unwinding<R>(
+  default: R, f: impl FnOnce() -> R,
+) -> R {
+  #[cfg(feature = "std")]
+  {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or(default)
+  }
+  #[cfg(not(feature = "std"))]
+  {
+    struct AbortOnUnwind;
+    impl Drop for AbortOnUnwind {
+      fn drop(&mut self) {
+        // Panicking while already panicking aborts.
+        panic!("beryllium: a callback panicked, aborting");
+      }
+    }
+    let _ = default;
+    let guard = AbortOnUnwind;
+    let out = f();
+    core::mem::forget(guard);
+    out
+  }
+}
+
+unsafe extern "C" fn filter_trampoline(
+  userdata: *mut c_void, sdl_event: *mut SDL_Event,
+) -> i32 {
+  let filter = &mut *(userdata as *mut Box<FilterFn>);
+  // Events we can't parse are always kept.
+  let event = match Event::peek_from_sdl(&*sdl_event) {
+    Ok(event) => event,
+    Err(()) => return 1,
+  };
+  call_without_unwinding(true, || filter(&event)) as i32
+}
+
+/// The filter we last gave to SDL, so that it can be freed later.
+static EVENT_FILTER: AtomicPtr<Box<FilterFn>> =
+  AtomicPtr::new(core::ptr::null_mut());
+
+/// Sets (or clears) the event filter, dropping the previous one.
+///
+/// SDL holds a lock while it runs the filter, and takes that same lock to
+/// change the filter, so the old closure isn't running once this returns.
+pub(crate) fn replace_event_filter(new: Option<FilterBox>) {
+  let new_ptr = new.map_or(core::ptr::null_mut(), Box::into_raw);
+  unsafe {
+    if new_ptr.is_null() {
+      fermium::SDL_SetEventFilter(None, core::ptr::null_mut());
+    } else {
+      fermium::SDL_SetEventFilter(Some(filter_trampoline), new_ptr.cast());
+    }
+  }
+  let old_ptr = EVENT_FILTER.swap(new_ptr, Ordering::SeqCst);
+  if !old_ptr.is_null() {
+    drop(unsafe { Box::from_raw(old_ptr) });
+  }
+}
+
+type WatchFn = dyn FnMut(&Event) + Send + 'static;
+
+unsafe extern "C" fn watch_trampoline(
+  userdata: *mut c_void, sdl_event: *mut SDL_Event,
+) -> i32 {
+  let watch = &mut *(userdata as *mut Box<WatchFn>);
+  if let Ok(event) = Event::peek_from_sdl(&*sdl_event) {
+    call_without_unwinding((), || watch(&event));
+  }
+  // SDL ignores the return value of watchers.
+  0
+}
+
+/// An event watch callback, from [`Sdl::add_event_watch`](crate::Sdl::add_event_watch).
+///
+/// Dropping this removes the watch.
+pub struct EventWatch {
+  userdata: NonNull<Box<WatchFn>>,
+  // Note(Lokathor): As long as the watch lives, we have to also keep SDL
+  // itself alive.
+  #[allow(dead_code)]
+  init: Arc<Initialization>,
+}
+impl core::fmt::Debug for EventWatch {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "EventWatch({:p})", self.userdata)
+  }
+}
+impl Drop for EventWatch {
+  // Note(Lokathor): The drop for the Arc runs *after* this drop code.
+  fn drop(&mut self) {
+    // SDL holds a lock while it runs watchers, and takes that same lock to
+    // remove one, so the closure isn't running once this returns.
+    unsafe {
+      fermium::SDL_DelEventWatch(
+        Some(watch_trampoline),
+        self.userdata.as_ptr().cast(),
+      );
+      drop(Box::from_raw(self.userdata.as_ptr()));
+    }
+  }
+}
+impl EventWatch {
+  pub(crate) fn add<F>(init: Arc<Initialization>, watch: F) -> Self
+  where
+    F: FnMut(&Event) + Send + 'static,
+  {
+    let boxed: Box<Box<WatchFn>> = Box::new(Box::new(watch));
+    let userdata = unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) };
+    unsafe {
+      fermium::SDL_AddEventWatch(
+        Some(watch_trampoline),
+        userdata.as_ptr().cast(),
+      )
+    };
+    Self { userdata, init }
+  }
+}
@@ -0,0 +1,113 @@
+use core::{
+  any::Any,
+  ops::{Bound, RangeBounds},
+};
+
+use crate::UserEventType;
+
+/// A type of event, as SDL numbers them.
+///
+/// These are ordered the same as SDL's numbering, so you can make ranges of
+/// them, eg: `EventKind::MOUSE_MOTION..=EventKind::MOUSE_WHEEL` for all of the
+/// mouse events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct EventKind(pub(crate) u32);
+// Note: `SDL_EventType` is `i32` on some targets, so the casts are needed.
+#[allow(clippy::unnecessary_cast)]
+impl EventKind {
+  pub const QUIT: Self = Self(fermium::SDL_QUIT as u32);
+  pub const APP_TERMINATING: Self = Self(fermium::SDL_APP_TERMINATING as u32);
+  pub const APP_LOW_MEMORY: Self = Self(fermium::SDL_APP_LOWMEMORY as u32);
+  pub const APP_WILL_ENTER_BACKGROUND: Self =
+    Self(fermium::SDL_APP_WILLENTERBACKGROUND as u32);
+  pub const APP_DID_ENTER_BACKGROUND: Self =
+    Self(fermium::SDL_APP_DIDENTERBACKGROUND as u32);
+  pub const APP_WILL_ENTER_FOREGROUND: Self =
+    Self(fermium::SDL_APP_WILLENTERFOREGROUND as u32);
+  pub const APP_DID_ENTER_FOREGROUND: Self =
+    Self(fermium::SDL_APP_DIDENTERFOREGROUND as u32);
+  pub const DISPLAY: Self = Self(fermium::SDL_DISPLAYEVENT as u32);
+  pub const WINDOW: Self = Self(fermium::SDL_WINDOWEVENT as u32);
+  pub const SYS_WM: Self = Self(fermium::SDL_SYSWMEVENT as u32);
+  pub const KEY_DOWN: Self = Self(fermium::SDL_KEYDOWN as u32);
+  pub const KEY_UP: Self = Self(fermium::SDL_KEYUP as u32);
+  pub const TEXT_EDITING: Self = Self(fermium::SDL_TEXTEDITING as u32);
+  pub const TEXT_INPUT: Self = Self(fermium::SDL_TEXTINPUT as u32);
+  pub const KEYMAP_CHANGED: Self = Self(fermium::SDL_KEYMAPCHANGED as u32);
+  pub const MOUSE_MOTION: Self = Self(fermium::SDL_MOUSEMOTION as u32);
+  pub const MOUSE_BUTTON_DOWN: Self = Self(fermium::SDL_MOUSEBUTTONDOWN as u32);
+  pub const MOUSE_BUTTON_UP: Self = Self(fermium::SDL_MOUSEBUTTONUP as u32);
+  pub const MOUSE_WHEEL: Self = Self(fermium::SDL_MOUSEWHEEL as u32);
+  pub const JOY_AXIS_MOTION: Self = Self(fermium::SDL_JOYAXISMOTION as u32);
+  pub const JOY_BALL_MOTION: Self = Self(fermium::SDL_JOYBALLMOTION as u32);
+  pub const JOY_HAT_MOTION: Self = Self(fermium::SDL_JOYHATMOTION as u32);
+  pub const JOY_BUTTON_DOWN: Self = Self(fermium::SDL_JOYBUTTONDOWN as u32);
+  pub const JOY_BUTTON_UP: Self = Self(fermium::SDL_JOYBUTTONUP as u32);
+  pub const JOY_DEVICE_ADDED: Self = Self(fermium::SDL_JOYDEVICEADDED as u32);
+  pub const JOY_DEVICE_REMOVED: Self =
+    Self(fermium::SDL_JOYDEVICEREMOVED as u32);
+  pub const CONTROLLER_AXIS_MOTION: Self =
+    Self(fermium::SDL_CONTROLLERAXISMOTION as u32);
+  pub const CONTROLLER_BUTTON_DOWN: Self =
+    Self(fermium::SDL_CONTROLLERBUTTONDOWN as u32);
+  pub const CONTROLLER_BUTTON_UP: Self =
+    Self(fermium::SDL_CONTROLLERBUTTONUP as u32);
+  pub const CONTROLLER_DEVICE_ADDED: Self =
+    Self(fermium::SDL_CONTROLLERDEVICEADDED as u32);
+  pub const CONTROLLER_DEVICE_REMOVED: Self =
+    Self(fermium::SDL_CONTROLLERDEVICEREMOVED as u32);
+  pub const CONTROLLER_DEVICE_REMAPPED: Self =
+    Self(fermium::SDL_CONTROLLERDEVICEREMAPPED as u32);
+  pub const FINGER_DOWN: Self = Self(fermium::SDL_FINGERDOWN as u32);
+  pub const FINGER_UP: Self = Self(fermium::SDL_FINGERUP as u32);
+  pub const FINGER_MOTION: Self = Self(fermium::SDL_FINGERMOTION as u32);
+  pub const DOLLAR_GESTURE: Self = Self(fermium::SDL_DOLLARGESTURE as u32);
+  pub const DOLLAR_RECORD: Self = Self(fermium::SDL_DOLLARRECORD as u32);
+  pub const MULTI_GESTURE: Self = Self(fermium::SDL_MULTIGESTURE as u32);
+  pub const CLIPBOARD_UPDATE: Self = Self(fermium::SDL_CLIPBOARDUPDATE as u32);
+  pub const DROP_FILE: Self = Self(fermium::SDL_DROPFILE as u32);
+  pub const DROP_TEXT: Self = Self(fermium::SDL_DROPTEXT as u32);
+  pub const DROP_BEGIN: Self = Self(fermium::SDL_DROPBEGIN as u32);
+  pub const DROP_COMPLETE: Self = Self(fermium::SDL_DROPCOMPLETE as u32);
+  pub const AUDIO_DEVICE_ADDED: Self =
+    Self(fermium::SDL_AUDIODEVICEADDED as u32);
+  pub const AUDIO_DEVICE_REMOVED: Self =
+    Self(fermium::SDL_AUDIODEVICEREMOVED as u32);
+  pub const SENSOR_UPDATE: Self = Self(fermium::SDL_SENSORUPDATE as u32);
+  pub const RENDER_TARGETS_RESET: Self =
+    Self(fermium::SDL_RENDER_TARGETS_RESET as u32);
+  pub const RENDER_DEVICE_RESET: Self =
+    Self(fermium::SDL_RENDER_DEVICE_RESET as u32);
+  /// The first of the user event types.
+  pub const USER: Self = Self(fermium::SDL_USEREVENT as u32);
+}
+impl EventKind {
+  /// The raw SDL event type number.
+  pub fn as_u32(self) -> u32 {
+    self.0
+  }
+}
+impl<T: Any + Send + Sync> From<UserEventType<T>> for EventKind {
+  fn from(user_type: UserEventType<T>) -> Self {
+    Self(user_type.event_type())
+  }
+}
+
+/// Turns a range of kinds into the inclusive `(min, max)` that SDL wants.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn event_kind_bounds(
+  range: impl RangeBounds<EventKind>,
+) -> (u32, u32) {
+  let min = match range.start_bound() {
+    Bound::Included(k) => k.0,
+    Bound::Excluded(k) => k.0.saturating_add(1),
+    Bound::Unbounded => fermium::SDL_FIRSTEVENT as u32,
+  };
+  let max = match range.end_bound() {
+    Bound::Included(k) => k.0,
+    Bound::Excluded(k) => k.0.saturating_sub(1),
+    Bound::Unbounded => fermium::SDL_LASTEVENT as u32,
+  };
+  (min, max)
+}
@@ -0,0 +1,270 @@
+use core::{convert::TryFrom, mem::size_of};
+
+use alloc::vec::Vec;
+
+use std::io::{self, Read, Write};
+
+use fermium::{
+  SDL_Event, SDL_EventType, SDL_DROPBEGIN, SDL_DROPCOMPLETE, SDL_DROPFILE,
+  SDL_DROPTEXT, SDL_LASTEVENT, SDL_SYSWMEVENT, SDL_USEREVENT,
+};
+
+use crate::{Event, Sdl, Timestamp};
+
+/// Marks the start of a recording, and which version of the format it is.
+const MAGIC: &[u8; 8] = b"BERYEV01";
+
+/// The longest drop string a recording can hold, so that a bad file can't
+/// make us allocate without limit.
+const MAX_DROP_TEXT_LEN: usize = 1 << 24;
+
+fn sdl_event_bytes(sdl_event: &SDL_Event) -> &[u8] {
+  unsafe {
+    core::slice::from_raw_parts(
+      (sdl_event as *const SDL_Event).cast::<u8>(),
+      size_of::<SDL_Event>(),
+    )
+  }
+}
+
+fn sdl_event_bytes_mut(sdl_event: &mut SDL_Event) -> &mut [u8] {
+  unsafe {
+    core::slice::from_raw_parts_mut(
+      (sdl_event as *mut SDL_Event).cast::<u8>(),
+      size_of::<SDL_Event>(),
+    )
+  }
+}
+
+fn bad_data(msg: &'static str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn corrupt() -> io::Error {
+  bad_data("beryllium: event recording is corrupt.")
+}
+
+/// As `read_exact`, but a file that ends part way through is corrupt.
+fn read_exact_or_corrupt(
+  input: &mut impl Read, buf: &mut [u8],
+) -> io::Result<()> {
+  input.read_exact(buf).map_err(|e| {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+      corrupt()
+    } else {
+      e
+    }
+  })
+}
+
+/// Polls events while also writing them out, to be replayed later with an
+/// [`EventReplayer`].
+///
+/// Use this in place of [`Sdl::poll_event`] while you want to record.
+///
+/// * Events are stored the same way SDL gives them to beryllium, so a replay
+///   goes through all the same parsing. This also means that recordings are
+///   only meant to be replayed on the same platform and SDL version.
+/// * User events and SysWM events hold pointers, so they're passed along but
+///   not recorded.
+#[derive(Debug)]
+pub struct EventRecorder<W: Write> {
+  out: W,
+}
+impl<W: Write> EventRecorder<W> {
+  /// Starts a new recording.
+  ///
+  /// ## Failure
+  /// * If the header can't be written.
+  pub fn new(mut out: W) -> io::Result<Self> {
+    out.write_all(MAGIC)?;
+    out.write_all(&(size_of::<SDL_Event>() as u32).to_le_bytes())?;
+    Ok(Self { out })
+  }
+
+  /// Polls for a pending event, recording it before it's parsed.
+  ///
+  /// This works like [`Sdl::poll_event`], except that events that can't be
+  /// parsed are skipped rather than giving `None`.
+  ///
+  /// ## Failure
+  /// * If the event can't be written. The event is lost in this case.
+  pub fn poll_event(
+    &mut self, sdl: &Sdl,
+  ) -> io::Result<Option<(Event, Timestamp)>> {
+    loop {
+      let mut sdl_event = SDL_Event::default();
+      if unsafe { fermium::SDL_PollEvent(&mut sdl_event) } == 0 {
+        return Ok(None);
+      }
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      let write_result = self.write_sdl_event(&sdl_event);
+      // Parse even if the write failed, so that any data the event holds is
+      // still freed.
+      let parsed = Event::try_from(sdl_event);
+      write_result?;
+      if let Ok(event) = parsed {
+        sdl.note_event(&event);
+        return Ok(Some((event, timestamp)));
+      }
+    }
+  }
+
+  fn write_sdl_event(&mut self, sdl_event: &SDL_Event) -> io::Result<()> {
+    let event_type = unsafe { sdl_event.type_ } as SDL_EventType;
+    if event_type == SDL_SYSWMEVENT
+      || (SDL_USEREVENT..SDL_LASTEVENT).contains(&event_type)
+    {
+      return Ok(());
+    }
+    if event_type == SDL_DROPFILE || event_type == SDL_DROPTEXT {
+      // Write the string after the event, and don't write out the pointer.
+      let mut copy = *sdl_event;
+      copy.drop.file = core::ptr::null_mut();
+      self.out.write_all(sdl_event_bytes(&copy))?;
+      let text = unsafe { c_str_bytes(sdl_event.drop.file.cast()) };
+      self.out.write_all(&(text.len() as u32).to_le_bytes())?;
+      self.out.write_all(text)
+    } else {
+      self.out.write_all(sdl_event_bytes(sdl_event))
+    }
+  }
+
+  /// Flushes the output.
+  pub fn flush(&mut self) -> io::Result<()> {
+    self.out.flush()
+  }
+
+  /// Ends the recording, giving back the output.
+  pub fn into_inner(self) -> W {
+    self.out
+  }
+}
+
+unsafe fn c_str_bytes<'a>(ptr: *const u8) -> &'a [u8] {
+  if ptr.is_null() {
+    return &[];
+  }
+  let mut len = 0;
+  while *ptr.add(len) != 0 {
+    len += 1;
+  }
+  core::slice::from_raw_parts(ptr, len)
+}
+
+/// Reads events back out of a recording made by an [`EventRecorder`].
+///
+/// Use this in place of live input. You can either take events as fast as
+/// you like with `next_event`, or use `poll_event` with your own clock to
+/// get them back at the same pace that they were recorded.
+#[derive(Debug)]
+pub struct EventReplayer<R: Read> {
+  input: R,
+  next: Option<(Event, Timestamp)>,
+}
+impl<R: Read> EventReplayer<R> {
+  /// Opens a recording.
+  ///
+  /// ## Failure
+  /// * If the header is missing, or is from an incompatible platform.
+  pub fn new(mut input: R) -> io::Result<Self> {
+    let mut magic = [0_u8; 8];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+      return Err(bad_data("beryllium: not an event recording."));
+    }
+    let mut size = [0_u8; 4];
+    input.read_exact(&mut size)?;
+    if u32::from_le_bytes(size) as usize != size_of::<SDL_Event>() {
+      return Err(bad_data(
+        "beryllium: event recording is from an incompatible platform.",
+      ));
+    }
+    Ok(Self { input, next: None })
+  }
+
+  /// Gives the next recorded event, no matter when it was recorded.
+  ///
+  /// * `None` means the recording is over.
+  /// * The [`Timestamp`] is the event's time from the recording.
+  ///
+  /// ## Failure
+  /// * If the recording is cut off part way through an event.
+  pub fn next_event(&mut self) -> io::Result<Option<(Event, Timestamp)>> {
+    match self.next.take() {
+      Some(next) => Ok(Some(next)),
+      None => self.read_event(),
+    }
+  }
+
+  /// Gives the next recorded event, if its timestamp is `now` or earlier.
+  ///
+  /// Pass [`Sdl::ticks`] (or the time since the replay began, if the
+  /// recording began at the start of the program) to play the events back at
+  /// the recorded pace.
+  pub fn poll_event(
+    &mut self, now: Timestamp,
+  ) -> io::Result<Option<(Event, Timestamp)>> {
+    if self.next.is_none() {
+      self.next = self.read_event()?;
+    }
+    match self.next {
+      Some((_, timestamp)) if timestamp <= now => Ok(self.next.take()),
+      _ => Ok(None),
+    }
+  }
+
+  fn read_event(&mut self) -> io::Result<Option<(Event, Timestamp)>> {
+    loop {
+      let mut sdl_event = SDL_Event::default();
+      let buf = sdl_event_bytes_mut(&mut sdl_event);
+      // The recording only ends cleanly between records, so a record that's
+      // cut off part way means the file is corrupt.
+      let mut filled = 0;
+      while filled < buf.len() {
+        match self.input.read(&mut buf[filled..]) {
+          Ok(0) if filled == 0 => return Ok(None),
+          Ok(0) => return Err(corrupt()),
+          Ok(n) => filled += n,
+          Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+          Err(e) => return Err(e),
+        }
+      }
+      let event_type = unsafe { sdl_event.type_ } as SDL_EventType;
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      // Note: Any pointers in the file are junk, so the events that
+      // hold them are never recorded, and anything else that has one gets it
+      // cleared before parsing.
+      if event_type == SDL_SYSWMEVENT
+        || (SDL_USEREVENT..SDL_LASTEVENT).contains(&event_type)
+      {
+        continue;
+      }
+      let mut text: Vec<u8> = Vec::new();
+      match event_type {
+        SDL_DROPFILE | SDL_DROPTEXT => {
+          let mut len = [0_u8; 4];
+          read_exact_or_corrupt(&mut self.input, &mut len)?;
+          let len = u32::from_le_bytes(len) as usize;
+          if len > MAX_DROP_TEXT_LEN {
+            return Err(corrupt());
+          }
+          text = alloc::vec![0; len + 1];
+          read_exact_or_corrupt(&mut self.input, &mut text[..len])?;
+          sdl_event.drop.file = text.as_mut_ptr().cast();
+        }
+        SDL_DROPBEGIN | SDL_DROPCOMPLETE => {
+          sdl_event.drop.file = core::ptr::null_mut();
+        }
+        _ => (),
+      }
+      // Safety: there's no user event, and any drop string is our own buffer,
+      // which this copies out of.
+      let parsed = unsafe { Event::peek_from_sdl(&sdl_event) };
+      drop(text);
+      if let Ok(event) = parsed {
+        return Ok(Some((event, timestamp)));
+      }
+    }
+  }
+}
@@ -0,0 +1,92 @@
+use core::{
+  pin::Pin,
+  sync::atomic::{AtomicBool, Ordering},
+  task::{Context, Poll, Waker},
+  time::Duration,
+};
+
+use alloc::sync::Arc;
+
+use std::sync::Mutex;
+
+use futures_core::Stream;
+
+use crate::{Event, EventWatch, Sdl, Timestamp};
+
+#[derive(Debug, Default)]
+struct Shared {
+  waker: Mutex<Option<Waker>>,
+  stop: AtomicBool,
+}
+impl Shared {
+  fn wake(&self) {
+    let waker = self.waker.lock().ok().and_then(|mut waker| waker.take());
+    if let Some(waker) = waker {
+      waker.wake();
+    }
+  }
+}
+
+/// A `Stream` of events, for using SDL from an async runtime.
+///
+/// Get one from [`Sdl::event_stream`]. The stream never ends on its own.
+///
+/// * Like `Sdl`, this isn't `Send`, so it must be polled on the thread that
+///   initialized SDL (eg: with a current-thread runtime, or a `LocalSet` in
+///   tokio).
+/// * Events pushed from other threads (such as user events) wake the task
+///   right away.
+/// * OS events only show up when SDL pumps them on this thread, so a helper
+///   thread wakes the task every `poll_interval` to check for them.
+#[derive(Debug)]
+pub struct EventStream<'s> {
+  sdl: &'s Sdl,
+  shared: Arc<Shared>,
+  _watch: EventWatch,
+}
+impl Drop for EventStream<'_> {
+  fn drop(&mut self) {
+    self.shared.stop.store(true, Ordering::SeqCst);
+  }
+}
+impl<'s> EventStream<'s> {
+  pub(crate) fn new(sdl: &'s Sdl, poll_interval: Duration) -> Self {
+    let shared = Arc::new(Shared::default());
+    let watch_shared = shared.clone();
+    let watch = sdl.add_event_watch(move |_| watch_shared.wake());
+    let timer_shared = shared.clone();
+    std::thread::spawn(move || {
+      while !timer_shared.stop.load(Ordering::SeqCst) {
+        std::thread::sleep(poll_interval);
+        timer_shared.wake();
+      }
+    });
+    Self { sdl, shared, _watch: watch }
+  }
+}
+impl Stream for EventStream<'_> {
+  type Item = (Event, Timestamp);
+  fn poll_next(
+    self: Pin<&mut Self>, cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    // Store the waker first, so that an event pushed after we poll the queue
+    // still wakes us.
+    if let Ok(mut waker) = self.shared.waker.lock() {
+      *waker = Some(cx.waker().clone());
+    }
+    match self.sdl.poll_events().next() {
+      Some(event) => Poll::Ready(Some(event)),
+      None => Poll::Pending,
+    }
+  }
+}
+
+impl Sdl {
+  /// Makes a `Stream` of events, see [`EventStream`].
+  ///
+  /// `poll_interval` is how often to check for OS events while there's
+  /// nothing else going on. Something close to your frame time is good.
+  pub fn event_stream(&self, poll_interval: Duration) -> EventStream<'_> {
+    EventStream::new(self, poll_interval)
+  }
+}
@@ -0,0 +1,520 @@
+use core::{marker::PhantomData, ptr::NonNull, time::Duration};
+
+use alloc::sync::Arc;
+
+use fermium::{
+  SDL_Haptic, SDL_HapticDirection, SDL_HapticEffect, SDL_HAPTIC_AUTOCENTER,
+  SDL_HAPTIC_CARTESIAN, SDL_HAPTIC_CONSTANT, SDL_HAPTIC_CUSTOM,
+  SDL_HAPTIC_DAMPER, SDL_HAPTIC_FRICTION, SDL_HAPTIC_GAIN, SDL_HAPTIC_INERTIA,
+  SDL_HAPTIC_INFINITY, SDL_HAPTIC_LEFTRIGHT, SDL_HAPTIC_PAUSE,
+  SDL_HAPTIC_POLAR, SDL_HAPTIC_RAMP, SDL_HAPTIC_SAWTOOTHDOWN,
+  SDL_HAPTIC_SAWTOOTHUP, SDL_HAPTIC_SINE, SDL_HAPTIC_SPHERICAL,
+  SDL_HAPTIC_SPRING, SDL_HAPTIC_STATUS, SDL_HAPTIC_TRIANGLE,
+};
+
+use crate::{sdl_get_error, Initialization, Joystick, SdlError};
+
+/// The effects and settings that a [`Haptic`] device supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct HapticFeatures(pub(crate) u32);
+impl HapticFeatures {
+  pub const CONSTANT: Self = Self(SDL_HAPTIC_CONSTANT);
+  pub const SINE: Self = Self(SDL_HAPTIC_SINE);
+  pub const LEFT_RIGHT: Self = Self(SDL_HAPTIC_LEFTRIGHT);
+  pub const TRIANGLE: Self = Self(SDL_HAPTIC_TRIANGLE);
+  pub const SAWTOOTH_UP: Self = Self(SDL_HAPTIC_SAWTOOTHUP);
+  pub const SAWTOOTH_DOWN: Self = Self(SDL_HAPTIC_SAWTOOTHDOWN);
+  pub const RAMP: Self = Self(SDL_HAPTIC_RAMP);
+  pub const SPRING: Self = Self(SDL_HAPTIC_SPRING);
+  pub const DAMPER: Self = Self(SDL_HAPTIC_DAMPER);
+  pub const INERTIA: Self = Self(SDL_HAPTIC_INERTIA);
+  pub const FRICTION: Self = Self(SDL_HAPTIC_FRICTION);
+  pub const CUSTOM: Self = Self(SDL_HAPTIC_CUSTOM);
+  /// [`Haptic::set_gain`] works.
+  pub const GAIN: Self = Self(SDL_HAPTIC_GAIN);
+  /// [`Haptic::set_autocenter`] works.
+  pub const AUTOCENTER: Self = Self(SDL_HAPTIC_AUTOCENTER);
+  /// [`Haptic::is_effect_playing`] works.
+  pub const STATUS: Self = Self(SDL_HAPTIC_STATUS);
+  /// [`Haptic::pause`] works.
+  pub const PAUSE: Self = Self(SDL_HAPTIC_PAUSE);
+
+  /// If all of the features in `other` are in this set.
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+}
+
+/// The direction that a haptic effect pushes from.
+///
+/// Not all devices use a direction (and rumble pads never do).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HapticDirection {
+  /// Hundredths of a degree, clockwise from north (0 to 35999).
+  Polar(i32),
+  /// An `[x, y, z]` vector, with positive x being east and positive y being
+  /// south.
+  Cartesian([i32; 3]),
+  /// Hundredths of a degree: the first is like `Polar` but from east, the
+  /// second is the angle up from the ground.
+  Spherical([i32; 2]),
+}
+impl Default for HapticDirection {
+  fn default() -> Self {
+    Self::Polar(0)
+  }
+}
+impl From<HapticDirection> for SDL_HapticDirection {
+  fn from(direction: HapticDirection) -> Self {
+    let (type_, dir) = match direction {
+      HapticDirection::Polar(angle) => (SDL_HAPTIC_POLAR, [angle, 0, 0]),
+      HapticDirection::Cartesian(xyz) => (SDL_HAPTIC_CARTESIAN, xyz),
+      HapticDirection::Spherical([a, b]) => (SDL_HAPTIC_SPHERICAL, [a, b, 0]),
+    };
+    SDL_HapticDirection { type_: type_ as u8, dir }
+  }
+}
+
+/// How an effect fades in and out.
+///
+/// The levels are strengths from 0 to `0x7FFF`, and the effect moves from
+/// the attack level to its own level, then out to the fade level. The
+/// default is no envelope at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HapticEnvelope {
+  pub attack_length: Duration,
+  pub attack_level: u16,
+  pub fade_length: Duration,
+  pub fade_level: u16,
+}
+
+/// The wave shape of a periodic effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HapticWave {
+  Sine,
+  Triangle,
+  SawtoothUp,
+  SawtoothDown,
+}
+
+/// A haptic effect, to upload to a [`Haptic`] device.
+///
+/// * `length` is how long the effect lasts each time it runs, with `None`
+///   meaning until it's stopped.
+/// * `delay` is the time before the effect starts.
+/// * Strengths (`level`, `magnitude`, and so on) go up to `0x7FFF`, and
+///   negative values push the other way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HapticEffect {
+  /// A steady push.
+  Constant {
+    direction: HapticDirection,
+    length: Option<Duration>,
+    delay: Duration,
+    level: i16,
+    envelope: HapticEnvelope,
+  },
+  /// A push that goes back and forth in a wave.
+  ///
+  /// `period` is the time of one wave, and `phase` shifts the wave along, in
+  /// hundredths of a degree.
+  Periodic {
+    wave: HapticWave,
+    direction: HapticDirection,
+    length: Option<Duration>,
+    delay: Duration,
+    period: Duration,
+    magnitude: i16,
+    offset: i16,
+    phase: u16,
+    envelope: HapticEnvelope,
+  },
+  /// A push that goes from the `start` level to the `end` level.
+  Ramp {
+    direction: HapticDirection,
+    length: Option<Duration>,
+    delay: Duration,
+    start: i16,
+    end: i16,
+    envelope: HapticEnvelope,
+  },
+  /// The two motors of a rumble pad, the large one being the low frequency
+  /// motor. These magnitudes go all the way up to `0xFFFF`.
+  LeftRight {
+    length: Option<Duration>,
+    large_magnitude: u16,
+    small_magnitude: u16,
+  },
+}
+impl From<HapticEffect> for SDL_HapticEffect {
+  fn from(effect: HapticEffect) -> Self {
+    let mut sdl_effect = SDL_HapticEffect::default();
+    match effect {
+      HapticEffect::Constant { direction, length, delay, level, envelope } => {
+        sdl_effect.constant = fermium::SDL_HapticConstant {
+          type_: SDL_HAPTIC_CONSTANT as u16,
+          direction: direction.into(),
+          length: length_millis(length),
+          delay: millis_u16(delay),
+          level,
+          attack_length: millis_u16(envelope.attack_length),
+          attack_level: envelope.attack_level,
+          fade_length: millis_u16(envelope.fade_length),
+          fade_level: envelope.fade_level,
+          ..Default::default()
+        }
+      }
+      HapticEffect::Periodic {
+        wave,
+        direction,
+        length,
+        delay,
+        period,
+        magnitude,
+        offset,
+        phase,
+        envelope,
+      } => {
+        let type_ = match wave {
+          HapticWave::Sine => SDL_HAPTIC_SINE,
+          HapticWave::Triangle => SDL_HAPTIC_TRIANGLE,
+          HapticWave::SawtoothUp => SDL_HAPTIC_SAWTOOTHUP,
+          HapticWave::SawtoothDown => SDL_HAPTIC_SAWTOOTHDOWN,
+        };
+        sdl_effect.periodic = fermium::SDL_HapticPeriodic {
+          type_: type_ as u16,
+          direction: direction.into(),
+          length: length_millis(length),
+          delay: millis_u16(delay),
+          period: millis_u16(period),
+          magnitude,
+          offset,
+          phase,
+          attack_length: millis_u16(envelope.attack_length),
+          attack_level: envelope.attack_level,
+          fade_length: millis_u16(envelope.fade_length),
+          fade_level: envelope.fade_level,
+          ..Default::default()
+        }
+      }
+      HapticEffect::Ramp { direction, length, delay, start, end, envelope } => {
+        sdl_effect.ramp = fermium::SDL_HapticRamp {
+          type_: SDL_HAPTIC_RAMP as u16,
+          direction: direction.into(),
+          length: length_millis(length),
+          delay: millis_u16(delay),
+          start,
+          end,
+          attack_length: millis_u16(envelope.attack_length),
+          attack_level: envelope.attack_level,
+          fade_length: millis_u16(envelope.fade_length),
+          fade_level: envelope.fade_level,
+          ..Default::default()
+        }
+      }
+      HapticEffect::LeftRight { length, large_magnitude, small_magnitude } => {
+        sdl_effect.leftright = fermium::SDL_HapticLeftRight {
+          type_: SDL_HAPTIC_LEFTRIGHT as u16,
+          length: length_millis(length),
+          large_magnitude,
+          small_magnitude,
+        }
+      }
+    }
+    sdl_effect
+  }
+}
+
+fn length_millis(length: Option<Duration>) -> u32 {
+  match length {
+    Some(length) => {
+      length.as_millis().min(u128::from(SDL_HAPTIC_INFINITY - 1)) as u32
+    }
+    None => SDL_HAPTIC_INFINITY,
+  }
+}
+
+fn millis_u16(duration: Duration) -> u16 {
+  duration.as_millis().min(u128::from(u16::MAX)) as u16
+}
+
+/// An effect that's been uploaded to a [`Haptic`] device.
+///
+/// It's only good for the device that it came from, and it goes away once
+/// that device is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct HapticEffectID(i32);
+
+/// A force feedback device, such as a wheel or a rumble pad.
+///
+/// Open one with [`Sdl::open_haptic`](crate::Sdl::open_haptic) or
+/// [`Joystick::open_haptic`]. Then upload effects to it, and run them by
+/// their [`HapticEffectID`].
+pub struct Haptic<'j> {
+  nn: NonNull<SDL_Haptic>,
+  // Note(Lokathor): As long as the device lives, we have to also keep SDL
+  // itself alive.
+  #[allow(dead_code)]
+  init: Arc<Initialization>,
+  // Note: When opened from a joystick, the joystick has to outlive
+  // this.
+  _joystick: PhantomData<&'j Joystick>,
+}
+impl Drop for Haptic<'_> {
+  // Note(Lokathor): The drop for the Arc runs *after* this drop code.
+  fn drop(&mut self) {
+    unsafe { fermium::SDL_HapticClose(self.nn.as_ptr()) }
+  }
+}
+impl Haptic<'_> {
+  pub(crate) fn open(
+    init: Arc<Initialization>, index: usize,
+  ) -> Result<Haptic<'static>, SdlError> {
+    NonNull::new(unsafe { fermium::SDL_HapticOpen(index as i32) })
+      .ok_or_else(sdl_get_error)
+      .map(|nn| Haptic { nn, init, _joystick: PhantomData })
+  }
+
+  pub(crate) fn from_joystick(
+    init: Arc<Initialization>, joystick: *mut fermium::SDL_Joystick,
+  ) -> Result<Self, SdlError> {
+    NonNull::new(unsafe { fermium::SDL_HapticOpenFromJoystick(joystick) })
+      .ok_or_else(sdl_get_error)
+      .map(|nn| Haptic { nn, init, _joystick: PhantomData })
+  }
+
+  /// The effects and settings this device supports.
+  pub fn features(&self) -> HapticFeatures {
+    HapticFeatures(unsafe { fermium::SDL_HapticQuery(self.nn.as_ptr()) })
+  }
+
+  /// If the device can play an effect.
+  pub fn is_effect_supported(&self, effect: &HapticEffect) -> bool {
+    let mut sdl_effect = SDL_HapticEffect::from(*effect);
+    let ret = unsafe {
+      fermium::SDL_HapticEffectSupported(self.nn.as_ptr(), &mut sdl_effect)
+    };
+    ret == 1
+  }
+
+  /// How many axes the device pushes along (for the directions of effects).
+  pub fn number_of_axes(&self) -> usize {
+    unsafe { fermium::SDL_HapticNumAxes(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  /// How many effects can be uploaded at once.
+  ///
+  /// This is only a rough guide, some devices have other limits too.
+  pub fn effect_capacity(&self) -> usize {
+    unsafe { fermium::SDL_HapticNumEffects(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  /// How many effects can be playing at once.
+  pub fn playing_capacity(&self) -> usize {
+    unsafe { fermium::SDL_HapticNumEffectsPlaying(self.nn.as_ptr()) }.max(0)
+      as usize
+  }
+
+  /// Uploads an effect to the device, so that it can be run.
+  ///
+  /// ## Failure
+  /// * If the effect isn't supported, or there's no room for it.
+  pub fn upload_effect(
+    &self, effect: &HapticEffect,
+  ) -> Result<HapticEffectID, SdlError> {
+    let mut sdl_effect = SDL_HapticEffect::from(*effect);
+    let ret = unsafe {
+      fermium::SDL_HapticNewEffect(self.nn.as_ptr(), &mut sdl_effect)
+    };
+    if ret >= 0 {
+      Ok(HapticEffectID(ret))
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Changes an uploaded effect, even while it's running.
+  ///
+  /// ## Failure
+  /// * The new effect has to be the same kind as the old one, you can't
+  ///   change (for example) a ramp into a constant effect.
+  pub fn update_effect(
+    &self, id: HapticEffectID, effect: &HapticEffect,
+  ) -> Result<(), SdlError> {
+    let mut sdl_effect = SDL_HapticEffect::from(*effect);
+    let ret = unsafe {
+      fermium::SDL_HapticUpdateEffect(self.nn.as_ptr(), id.0, &mut sdl_effect)
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Runs an uploaded effect, `iterations` times over (or until stopped, with
+  /// `None`).
+  pub fn run_effect(
+    &self, id: HapticEffectID, iterations: Option<u32>,
+  ) -> Result<(), SdlError> {
+    let iterations = iterations.unwrap_or(SDL_HAPTIC_INFINITY);
+    let ret = unsafe {
+      fermium::SDL_HapticRunEffect(self.nn.as_ptr(), id.0, iterations)
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Stops an effect that's running.
+  pub fn stop_effect(&self, id: HapticEffectID) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticStopEffect(self.nn.as_ptr(), id.0) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Stops and removes an uploaded effect, making room for another.
+  pub fn destroy_effect(&self, id: HapticEffectID) {
+    unsafe { fermium::SDL_HapticDestroyEffect(self.nn.as_ptr(), id.0) }
+  }
+
+  /// If an effect is running.
+  ///
+  /// ## Failure
+  /// * If the device doesn't have [`HapticFeatures::STATUS`].
+  pub fn is_effect_playing(
+    &self, id: HapticEffectID,
+  ) -> Result<bool, SdlError> {
+    let ret =
+      unsafe { fermium::SDL_HapticGetEffectStatus(self.nn.as_ptr(), id.0) };
+    if ret >= 0 {
+      Ok(ret == 1)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Stops all running effects.
+  pub fn stop_all(&self) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticStopAll(self.nn.as_ptr()) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Sets the overall strength of all effects, from 0 to 100 percent.
+  ///
+  /// ## Failure
+  /// * If the device doesn't have [`HapticFeatures::GAIN`].
+  pub fn set_gain(&self, percent: u8) -> Result<(), SdlError> {
+    let percent = i32::from(percent.min(100));
+    let ret = unsafe { fermium::SDL_HapticSetGain(self.nn.as_ptr(), percent) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Sets how strongly the device pulls back to center, from 0 (off) to 100
+  /// percent.
+  ///
+  /// ## Failure
+  /// * If the device doesn't have [`HapticFeatures::AUTOCENTER`].
+  pub fn set_autocenter(&self, percent: u8) -> Result<(), SdlError> {
+    let percent = i32::from(percent.min(100));
+    let ret =
+      unsafe { fermium::SDL_HapticSetAutocenter(self.nn.as_ptr(), percent) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Pauses all effects, such as while the game is paused.
+  ///
+  /// Don't change effects while paused.
+  ///
+  /// ## Failure
+  /// * If the device doesn't have [`HapticFeatures::PAUSE`].
+  pub fn pause(&self) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticPause(self.nn.as_ptr()) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Carries on with effects after a [`pause`](Haptic::pause).
+  pub fn unpause(&self) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticUnpause(self.nn.as_ptr()) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// If the simple rumble functions work on this device.
+  pub fn is_rumble_supported(&self) -> bool {
+    unsafe { fermium::SDL_HapticRumbleSupported(self.nn.as_ptr()) == 1 }
+  }
+
+  /// Gets the device ready for [`rumble_play`](Haptic::rumble_play).
+  ///
+  /// This is the easy way to shake a device, when you don't need full
+  /// effects. It uses up one of the device's effect slots.
+  ///
+  /// ## Failure
+  /// * If the device can't rumble.
+  pub fn rumble_init(&self) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticRumbleInit(self.nn.as_ptr()) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Rumbles the device, with a strength from `0.0` to `1.0`.
+  ///
+  /// A new rumble replaces one that's still going.
+  pub fn rumble_play(
+    &self, strength: f32, length: Duration,
+  ) -> Result<(), SdlError> {
+    let strength = strength.clamp(0.0, 1.0);
+    let ret = unsafe {
+      fermium::SDL_HapticRumblePlay(
+        self.nn.as_ptr(),
+        strength,
+        length_millis(Some(length)),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Stops the rumble early.
+  pub fn rumble_stop(&self) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_HapticRumbleStop(self.nn.as_ptr()) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+}
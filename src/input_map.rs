@@ -0,0 +1,254 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+  ControllerAxis, ControllerButton, ControllerDeviceEvent, Event, JoystickID,
+  MouseButtons, Scancode, WindowEvent,
+};
+
+/// Which way along a controller axis a binding reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisDirection {
+  /// Right or down on a stick, or pulling a trigger.
+  Positive,
+  /// Left or up on a stick.
+  Negative,
+}
+
+/// One physical input that can trigger an action in an [`InputMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBinding {
+  /// A key, by physical location.
+  Key(Scancode),
+  /// A mouse button (if more than one is given, any of them).
+  Mouse(MouseButtons),
+  /// A button on any controller.
+  ControllerButton(ControllerButton),
+  /// One direction of an axis on any controller.
+  ///
+  /// Axis values with a size of `dead_zone` or less count as zero, and the
+  /// rest of the range is scaled to `0.0 ..= 1.0`.
+  ControllerAxis {
+    axis: ControllerAxis,
+    direction: AxisDirection,
+    dead_zone: i16,
+  },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Action {
+  name: String,
+  bindings: Vec<InputBinding>,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  was_pressed: bool,
+}
+
+/// What's currently held, as seen from events.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct HeldInputs {
+  keys: Vec<Scancode>,
+  mouse: MouseButtons,
+  buttons: Vec<(JoystickID, ControllerButton)>,
+  axes: Vec<(JoystickID, ControllerAxis, i16)>,
+}
+impl HeldInputs {
+  fn binding_value(&self, binding: &InputBinding) -> f32 {
+    let held = match *binding {
+      InputBinding::Key(scancode) => self.keys.contains(&scancode),
+      InputBinding::Mouse(buttons) => self.mouse.0 & buttons.0 != 0,
+      InputBinding::ControllerButton(button) => {
+        self.buttons.iter().any(|&(_, b)| b == button)
+      }
+      InputBinding::ControllerAxis { axis, direction, dead_zone } => {
+        return self
+          .axes
+          .iter()
+          .filter(|&&(_, a, _)| a == axis)
+          .map(|&(_, _, value)| axis_value(value, direction, dead_zone))
+          .fold(0.0, f32::max)
+      }
+    };
+    if held {
+      1.0
+    } else {
+      0.0
+    }
+  }
+}
+
+/// How far an axis is pushed in one direction, past the dead zone.
+fn axis_value(value: i16, direction: AxisDirection, dead_zone: i16) -> f32 {
+  let (value, max) = match direction {
+    AxisDirection::Positive => (i32::from(value), i32::from(i16::MAX)),
+    AxisDirection::Negative => (-i32::from(value), -i32::from(i16::MIN)),
+  };
+  let dead_zone = i32::from(dead_zone.max(0));
+  if value <= dead_zone || dead_zone >= max {
+    0.0
+  } else {
+    ((value - dead_zone) as f32 / (max - dead_zone) as f32).min(1.0)
+  }
+}
+
+/// Binds named actions ("jump", "fire") to keys, mouse buttons, and
+/// controller inputs.
+///
+/// * Pass every event to [`handle_event`](InputMap::handle_event), then ask
+///   about actions with `pressed`, `just_pressed`, `just_released`, and
+///   `value`.
+/// * Call [`end_frame`](InputMap::end_frame) at the end of each frame, so
+///   that the "just" queries are relative to the frame before.
+/// * Controller bindings are read from all controllers.
+/// * With the `serde` feature the bindings can be saved and loaded, so that
+///   users can rebind them. The held input state isn't saved.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+  actions: Vec<Action>,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  held: HeldInputs,
+}
+impl InputMap {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn action(&self, name: &str) -> Option<&Action> {
+    self.actions.iter().find(|action| action.name == name)
+  }
+
+  /// Adds a binding to an action, making the action if it's new.
+  pub fn bind(&mut self, name: &str, binding: InputBinding) {
+    match self.actions.iter_mut().find(|action| action.name == name) {
+      Some(action) => {
+        if !action.bindings.contains(&binding) {
+          action.bindings.push(binding);
+        }
+      }
+      None => self.actions.push(Action {
+        name: String::from(name),
+        bindings: alloc::vec![binding],
+        was_pressed: false,
+      }),
+    }
+  }
+
+  /// Removes one binding from an action.
+  pub fn unbind(&mut self, name: &str, binding: InputBinding) {
+    if let Some(action) =
+      self.actions.iter_mut().find(|action| action.name == name)
+    {
+      action.bindings.retain(|&b| b != binding);
+    }
+  }
+
+  /// Removes all of an action's bindings. The action itself is kept.
+  pub fn clear_bindings(&mut self, name: &str) {
+    if let Some(action) =
+      self.actions.iter_mut().find(|action| action.name == name)
+    {
+      action.bindings.clear();
+    }
+  }
+
+  /// The bindings of an action (empty if there's no such action).
+  pub fn bindings(&self, name: &str) -> &[InputBinding] {
+    self.action(name).map_or(&[], |action| &action.bindings)
+  }
+
+  /// The names of all the actions.
+  pub fn action_names(&self) -> impl Iterator<Item = &str> + '_ {
+    self.actions.iter().map(|action| action.name.as_str())
+  }
+
+  /// Updates the held inputs from an event.
+  pub fn handle_event(&mut self, event: &Event) {
+    let held = &mut self.held;
+    match event {
+      Event::Keyboard(keyboard) => {
+        held.keys.retain(|&scancode| scancode != keyboard.scancode);
+        if keyboard.is_pressed {
+          held.keys.push(keyboard.scancode);
+        }
+      }
+      Event::MouseButton(mouse_button) => {
+        let button = MouseButtons::from_button_number(mouse_button.button);
+        if mouse_button.is_pressed {
+          held.mouse.0 |= button.0;
+        } else {
+          held.mouse.0 &= !button.0;
+        }
+      }
+      Event::ControllerButton(controller_button) => {
+        let key = (controller_button.joystick_id, controller_button.button);
+        held.buttons.retain(|&b| b != key);
+        if controller_button.is_pressed {
+          held.buttons.push(key);
+        }
+      }
+      Event::ControllerAxis(controller_axis) => {
+        let (joystick_id, axis) =
+          (controller_axis.joystick_id, controller_axis.axis);
+        held.axes.retain(|&(j, a, _)| (j, a) != (joystick_id, axis));
+        if controller_axis.value != 0 {
+          held.axes.push((joystick_id, axis, controller_axis.value));
+        }
+      }
+      Event::ControllerDevice(ControllerDeviceEvent::Removed {
+        joystick_id,
+      }) => {
+        held.buttons.retain(|&(j, _)| j != *joystick_id);
+        held.axes.retain(|&(j, _, _)| j != *joystick_id);
+      }
+      // Key and button releases would go to some other program.
+      Event::Window(WindowEvent::FocusLost { .. }) => {
+        held.keys.clear();
+        held.mouse = MouseButtons::NONE;
+      }
+      _ => (),
+    }
+  }
+
+  /// Marks the end of a frame.
+  pub fn end_frame(&mut self) {
+    for i in 0..self.actions.len() {
+      let pressed = self.action_value(&self.actions[i]) > 0.0;
+      self.actions[i].was_pressed = pressed;
+    }
+  }
+
+  fn action_value(&self, action: &Action) -> f32 {
+    action
+      .bindings
+      .iter()
+      .map(|binding| self.held.binding_value(binding))
+      .fold(0.0, f32::max)
+  }
+
+  /// How strongly an action is pressed, from `0.0` to `1.0`.
+  ///
+  /// Keys and buttons are all or nothing, axes can be in between. If more
+  /// than one binding is active, this is the strongest one.
+  pub fn value(&self, name: &str) -> f32 {
+    self.action(name).map_or(0.0, |action| self.action_value(action))
+  }
+
+  /// If any of the action's bindings are active.
+  pub fn pressed(&self, name: &str) -> bool {
+    self.value(name) > 0.0
+  }
+
+  /// If the action is pressed, and wasn't at the end of the last frame.
+  pub fn just_pressed(&self, name: &str) -> bool {
+    matches!(self.action(name), Some(action)
+      if !action.was_pressed && self.action_value(action) > 0.0)
+  }
+
+  /// If the action isn't pressed, but was at the end of the last frame.
+  pub fn just_released(&self, name: &str) -> bool {
+    matches!(self.action(name), Some(action)
+      if action.was_pressed && self.action_value(action) <= 0.0)
+  }
+}
@@ -0,0 +1,144 @@
+use alloc::vec::Vec;
+
+use crate::{Event, MouseButtons, Scancode, WindowEvent};
+
+/// Tracks keyboard and mouse input from frame to frame.
+///
+/// Pass every event to [`handle_event`](InputState::handle_event), use the
+/// queries while updating the frame, and then call
+/// [`end_frame`](InputState::end_frame).
+///
+/// * "This frame" means since the last `end_frame`. A key that's pressed and
+///   released within one frame counts as both pressed and released this
+///   frame, even though it's not down.
+/// * Mouse motion and wheel deltas add up over the frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputState {
+  keys_down: Vec<Scancode>,
+  keys_pressed: Vec<Scancode>,
+  keys_released: Vec<Scancode>,
+  buttons_down: MouseButtons,
+  buttons_pressed: MouseButtons,
+  buttons_released: MouseButtons,
+  mouse_position: [i32; 2],
+  mouse_delta: [i32; 2],
+  wheel_delta: [i32; 2],
+}
+impl InputState {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Updates the state from an event.
+  pub fn handle_event(&mut self, event: &Event) {
+    match event {
+      Event::Keyboard(keyboard) if !keyboard.is_repeat => {
+        let scancode = keyboard.scancode;
+        if keyboard.is_pressed {
+          if !self.keys_down.contains(&scancode) {
+            self.keys_down.push(scancode);
+          }
+          self.keys_pressed.push(scancode);
+        } else {
+          self.keys_down.retain(|&s| s != scancode);
+          self.keys_released.push(scancode);
+        }
+      }
+      Event::MouseButton(mouse_button) => {
+        let button = MouseButtons::from_button_number(mouse_button.button);
+        if mouse_button.is_pressed {
+          self.buttons_down |= button;
+          self.buttons_pressed |= button;
+        } else {
+          self.buttons_down.0 &= !button.0;
+          self.buttons_released |= button;
+        }
+        self.mouse_position = [mouse_button.x_pos, mouse_button.y_pos];
+      }
+      Event::MouseMotion(mouse_motion) => {
+        self.mouse_position = [mouse_motion.x_pos, mouse_motion.y_pos];
+        self.mouse_delta[0] =
+          self.mouse_delta[0].saturating_add(mouse_motion.dx);
+        self.mouse_delta[1] =
+          self.mouse_delta[1].saturating_add(mouse_motion.dy);
+      }
+      Event::MouseWheel(mouse_wheel) => {
+        self.wheel_delta[0] =
+          self.wheel_delta[0].saturating_add(mouse_wheel.dx);
+        self.wheel_delta[1] =
+          self.wheel_delta[1].saturating_add(mouse_wheel.dy);
+      }
+      // Releases would go to some other program, so count everything as
+      // released now.
+      Event::Window(WindowEvent::FocusLost { .. }) => {
+        self.keys_released.append(&mut self.keys_down);
+        self.buttons_released |= self.buttons_down;
+        self.buttons_down = MouseButtons::NONE;
+      }
+      _ => (),
+    }
+  }
+
+  /// Marks the end of a frame, clearing the "this frame" state.
+  pub fn end_frame(&mut self) {
+    self.keys_pressed.clear();
+    self.keys_released.clear();
+    self.buttons_pressed = MouseButtons::NONE;
+    self.buttons_released = MouseButtons::NONE;
+    self.mouse_delta = [0, 0];
+    self.wheel_delta = [0, 0];
+  }
+
+  /// If the key is held down.
+  pub fn key_down(&self, scancode: Scancode) -> bool {
+    self.keys_down.contains(&scancode)
+  }
+
+  /// If the key was pressed this frame. Key repeats don't count.
+  pub fn key_pressed_this_frame(&self, scancode: Scancode) -> bool {
+    self.keys_pressed.contains(&scancode)
+  }
+
+  /// If the key was released this frame.
+  pub fn key_released_this_frame(&self, scancode: Scancode) -> bool {
+    self.keys_released.contains(&scancode)
+  }
+
+  /// If any of the mouse buttons are held down.
+  pub fn mouse_button_down(&self, buttons: MouseButtons) -> bool {
+    self.buttons_down.0 & buttons.0 != 0
+  }
+
+  /// If any of the mouse buttons were pressed this frame.
+  pub fn mouse_button_pressed_this_frame(&self, buttons: MouseButtons) -> bool {
+    self.buttons_pressed.0 & buttons.0 != 0
+  }
+
+  /// If any of the mouse buttons were released this frame.
+  pub fn mouse_button_released_this_frame(
+    &self, buttons: MouseButtons,
+  ) -> bool {
+    self.buttons_released.0 & buttons.0 != 0
+  }
+
+  /// The last known mouse position, in window coordinates.
+  pub fn mouse_position(&self) -> [i32; 2] {
+    self.mouse_position
+  }
+
+  /// How far the mouse moved this frame.
+  ///
+  /// This keeps working in relative mouse mode, where the position doesn't
+  /// change.
+  pub fn mouse_delta(&self) -> [i32; 2] {
+    self.mouse_delta
+  }
+
+  /// How far the mouse wheel scrolled this frame, as `[dx, dy]`.
+  ///
+  /// This follows the same directions as a
+  /// [`MouseWheelEvent`](crate::MouseWheelEvent).
+  pub fn wheel_delta(&self) -> [i32; 2] {
+    self.wheel_delta
+  }
+}
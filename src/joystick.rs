@@ -0,0 +1,181 @@
+use core::{convert::TryFrom, ptr::NonNull};
+
+use alloc::{string::String, sync::Arc};
+
+use fermium::{
+  SDL_Joystick, SDL_JoystickPowerLevel, SDL_JOYSTICK_POWER_EMPTY,
+  SDL_JOYSTICK_POWER_FULL, SDL_JOYSTICK_POWER_LOW, SDL_JOYSTICK_POWER_MEDIUM,
+  SDL_JOYSTICK_POWER_WIRED,
+};
+
+use crate::{
+  sdl_get_error, string_from_sdl, Haptic, HatValue, Initialization,
+  JoystickDeviceIndex, JoystickGuid, JoystickID, SdlError,
+};
+
+/// The battery level of a joystick or controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PowerLevel {
+  Unknown,
+  Empty,
+  Low,
+  Medium,
+  Full,
+  /// Plugged in, so there's no battery to worry about.
+  Wired,
+}
+impl From<SDL_JoystickPowerLevel> for PowerLevel {
+  #[inline]
+  fn from(power_level: SDL_JoystickPowerLevel) -> Self {
+    match power_level {
+      SDL_JOYSTICK_POWER_EMPTY => Self::Empty,
+      SDL_JOYSTICK_POWER_LOW => Self::Low,
+      SDL_JOYSTICK_POWER_MEDIUM => Self::Medium,
+      SDL_JOYSTICK_POWER_FULL => Self::Full,
+      SDL_JOYSTICK_POWER_WIRED => Self::Wired,
+      _ => Self::Unknown,
+    }
+  }
+}
+
+/// A joystick, read as plain axes, buttons, hats, and trackballs.
+///
+/// This is for devices that don't fit the [`Controller`](crate::Controller)
+/// layout, such as flight sticks and wheels. What each input does depends on
+/// the device, so you'll generally need the user to pick which one is which.
+pub struct Joystick {
+  nn: NonNull<SDL_Joystick>,
+  // Note(Lokathor): As long as the joystick lives, we have to also keep SDL
+  // itself alive.
+  #[allow(dead_code)]
+  init: Arc<Initialization>,
+}
+impl Drop for Joystick {
+  // Note(Lokathor): The drop for the Arc runs *after* this drop code.
+  fn drop(&mut self) {
+    unsafe { fermium::SDL_JoystickClose(self.nn.as_ptr()) }
+  }
+}
+impl Joystick {
+  pub(crate) fn open(
+    init: Arc<Initialization>, index: JoystickDeviceIndex,
+  ) -> Result<Self, SdlError> {
+    let index = index.to_sdl()?;
+    NonNull::new(unsafe { fermium::SDL_JoystickOpen(index) })
+      .ok_or_else(sdl_get_error)
+      .map(|nn| Joystick { init, nn })
+  }
+
+  /// The instance ID of this joystick, as used by joystick events.
+  pub fn joystick_id(&self) -> JoystickID {
+    JoystickID(unsafe { fermium::SDL_JoystickInstanceID(self.nn.as_ptr()) })
+  }
+
+  /// The GUID of this kind of joystick.
+  pub fn guid(&self) -> JoystickGuid {
+    JoystickGuid(unsafe { fermium::SDL_JoystickGetGUID(self.nn.as_ptr()) }.data)
+  }
+
+  /// The name of the joystick, if it has one.
+  pub fn name(&self) -> Option<String> {
+    let p = unsafe { fermium::SDL_JoystickName(self.nn.as_ptr()) };
+    if p.is_null() {
+      None
+    } else {
+      Some(unsafe { string_from_sdl(p.cast()) })
+    }
+  }
+
+  /// If the joystick is still plugged in.
+  pub fn is_attached(&self) -> bool {
+    let attached =
+      unsafe { fermium::SDL_JoystickGetAttached(self.nn.as_ptr()) };
+    attached == fermium::SDL_TRUE
+  }
+
+  /// If the joystick has force feedback, see [`open_haptic`](Joystick::open_haptic).
+  pub fn is_haptic(&self) -> bool {
+    unsafe { fermium::SDL_JoystickIsHaptic(self.nn.as_ptr()) == 1 }
+  }
+
+  /// Opens the force feedback of the joystick.
+  ///
+  /// The joystick has to stay open while this is in use.
+  ///
+  /// ## Failure
+  /// * If the joystick has no force feedback.
+  pub fn open_haptic(&self) -> Result<Haptic<'_>, SdlError> {
+    Haptic::from_joystick(self.init.clone(), self.nn.as_ptr())
+  }
+
+  /// The battery level, such as for a low battery warning.
+  ///
+  /// Many devices don't report this, and give `Unknown`.
+  pub fn power_level(&self) -> PowerLevel {
+    PowerLevel::from(unsafe {
+      fermium::SDL_JoystickCurrentPowerLevel(self.nn.as_ptr())
+    })
+  }
+
+  pub fn number_of_axes(&self) -> usize {
+    unsafe { fermium::SDL_JoystickNumAxes(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  pub fn number_of_buttons(&self) -> usize {
+    unsafe { fermium::SDL_JoystickNumButtons(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  pub fn number_of_hats(&self) -> usize {
+    unsafe { fermium::SDL_JoystickNumHats(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  pub fn number_of_balls(&self) -> usize {
+    unsafe { fermium::SDL_JoystickNumBalls(self.nn.as_ptr()) }.max(0) as usize
+  }
+
+  /// The position of an axis, over the full `i16` range.
+  ///
+  /// Out of range axes are 0.
+  pub fn axis(&self, index: usize) -> i16 {
+    unsafe { fermium::SDL_JoystickGetAxis(self.nn.as_ptr(), index as i32) }
+  }
+
+  /// If a button is held down.
+  ///
+  /// Out of range buttons aren't.
+  pub fn button(&self, index: usize) -> bool {
+    let state =
+      unsafe { fermium::SDL_JoystickGetButton(self.nn.as_ptr(), index as i32) };
+    state == fermium::SDL_PRESSED as u8
+  }
+
+  /// The position of a hat.
+  ///
+  /// Out of range hats are centered.
+  pub fn hat(&self, index: usize) -> HatValue {
+    let value =
+      unsafe { fermium::SDL_JoystickGetHat(self.nn.as_ptr(), index as i32) };
+    HatValue::try_from(value).unwrap_or(HatValue::Centered)
+  }
+
+  /// How far a trackball has moved since the last call, as `[dx, dy]`.
+  ///
+  /// ## Failure
+  /// * If the index is out of range.
+  pub fn ball(&self, index: usize) -> Result<[i32; 2], SdlError> {
+    let mut delta = [0; 2];
+    let ret = unsafe {
+      fermium::SDL_JoystickGetBall(
+        self.nn.as_ptr(),
+        index as i32,
+        &mut delta[0],
+        &mut delta[1],
+      )
+    };
+    if ret >= 0 {
+      Ok(delta)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+}
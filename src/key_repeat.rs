@@ -0,0 +1,122 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+
+use crate::{Event, KeyboardEvent, Timestamp, WindowEvent};
+
+/// What a [`KeyRepeater`] does with held keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyRepeatMode {
+  /// Key repeats come through as the OS sends them.
+  PassThrough,
+  /// Key repeats are dropped, so each press is a single key down.
+  Suppress,
+  /// The OS key repeats are dropped, and new ones are made on our own timing.
+  ///
+  /// * `delay` is the time from the press until the first repeat.
+  /// * `interval` is the time between repeats after that.
+  Synthesize { delay: Duration, interval: Duration },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeldKey {
+  event: KeyboardEvent,
+  next: Timestamp,
+}
+
+/// Gives key repeats the way you want, no matter the OS settings.
+///
+/// Pass each event through [`filter_event`](KeyRepeater::filter_event) and
+/// skip the ones it says to drop. Then, once per frame, call
+/// [`poll_repeat`](KeyRepeater::poll_repeat) with [`Sdl::ticks`](crate::Sdl::ticks)
+/// to get any key repeats that this makes up.
+#[derive(Debug, Clone)]
+pub struct KeyRepeater {
+  mode: KeyRepeatMode,
+  held: Vec<HeldKey>,
+}
+impl KeyRepeater {
+  pub fn new(mode: KeyRepeatMode) -> Self {
+    Self { mode, held: Vec::new() }
+  }
+
+  pub fn mode(&self) -> KeyRepeatMode {
+    self.mode
+  }
+
+  /// Changes the mode. Keys that are already held won't repeat.
+  pub fn set_mode(&mut self, mode: KeyRepeatMode) {
+    self.mode = mode;
+    self.held.clear();
+  }
+
+  /// Checks an event, giving `false` if it should be dropped.
+  ///
+  /// Only key repeats are ever dropped. In `Synthesize` mode this also notes
+  /// which keys are held. Losing window focus stops all repeats, since the
+  /// key releases would go to some other program.
+  pub fn filter_event(&mut self, event: &Event, timestamp: Timestamp) -> bool {
+    match (self.mode, event) {
+      (KeyRepeatMode::PassThrough, _) => true,
+      (KeyRepeatMode::Suppress, Event::Keyboard(keyboard)) => {
+        !keyboard.is_repeat
+      }
+      (KeyRepeatMode::Suppress, _) => true,
+      (KeyRepeatMode::Synthesize { delay, .. }, Event::Keyboard(keyboard)) => {
+        if keyboard.is_repeat {
+          return false;
+        }
+        self.held.retain(|held| held.event.scancode != keyboard.scancode);
+        if keyboard.is_pressed {
+          let next = add_millis(timestamp, delay);
+          self.held.push(HeldKey { event: *keyboard, next });
+        }
+        true
+      }
+      (
+        KeyRepeatMode::Synthesize { .. },
+        Event::Window(WindowEvent::FocusLost { .. }),
+      ) => {
+        self.held.clear();
+        true
+      }
+      (KeyRepeatMode::Synthesize { .. }, _) => true,
+    }
+  }
+
+  /// Gives the next made up key repeat that's due at `now`, if any.
+  ///
+  /// * Call this until it gives `None`.
+  /// * The [`Timestamp`] is when the repeat was due.
+  /// * If you fall behind, repeats are skipped rather than bunched up.
+  pub fn poll_repeat(&mut self, now: Timestamp) -> Option<(Event, Timestamp)> {
+    let interval = match self.mode {
+      KeyRepeatMode::Synthesize { interval, .. } => interval,
+      _ => return None,
+    };
+    // The key that's been due the longest goes first.
+    let held = self
+      .held
+      .iter_mut()
+      .filter(|held| is_due(held.next, now))
+      .max_by_key(|held| now.since(held.next))?;
+    let timestamp = held.next;
+    held.next = add_millis(timestamp, interval);
+    if is_due(held.next, now) {
+      held.next = add_millis(now, interval);
+    }
+    let event = KeyboardEvent { is_repeat: true, ..held.event };
+    Some((Event::Keyboard(event), timestamp))
+  }
+}
+
+/// Adds a duration to a timestamp, as at least one millisecond.
+fn add_millis(timestamp: Timestamp, duration: Duration) -> Timestamp {
+  let millis = duration.as_millis().max(1).min(u128::from(u32::MAX)) as u32;
+  Timestamp(timestamp.0.wrapping_add(millis))
+}
+
+/// If `at` is `now` or earlier, allowing for the clock wrapping around.
+fn is_due(at: Timestamp, now: Timestamp) -> bool {
+  now.0.wrapping_sub(at.0) < 1 << 31
+}
@@ -0,0 +1,1376 @@
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+use alloc::vec::Vec;
+
+/// A physical key location, named for where it is on a US keyboard.
+///
+/// Use these for controls that should stay in the same place no matter the
+/// keyboard layout (eg: WASD movement). Codes that SDL doesn't name come out
+/// as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum Scancode {
+  Unknown = 0,
+  A = 4,
+  B = 5,
+  C = 6,
+  D = 7,
+  E = 8,
+  F = 9,
+  G = 10,
+  H = 11,
+  I = 12,
+  J = 13,
+  K = 14,
+  L = 15,
+  M = 16,
+  N = 17,
+  O = 18,
+  P = 19,
+  Q = 20,
+  R = 21,
+  S = 22,
+  T = 23,
+  U = 24,
+  V = 25,
+  W = 26,
+  X = 27,
+  Y = 28,
+  Z = 29,
+  Num1 = 30,
+  Num2 = 31,
+  Num3 = 32,
+  Num4 = 33,
+  Num5 = 34,
+  Num6 = 35,
+  Num7 = 36,
+  Num8 = 37,
+  Num9 = 38,
+  Num0 = 39,
+  Return = 40,
+  Escape = 41,
+  Backspace = 42,
+  Tab = 43,
+  Space = 44,
+  Minus = 45,
+  Equals = 46,
+  LeftBracket = 47,
+  RightBracket = 48,
+  Backslash = 49,
+  NonUsHash = 50,
+  Semicolon = 51,
+  Apostrophe = 52,
+  Grave = 53,
+  Comma = 54,
+  Period = 55,
+  Slash = 56,
+  CapsLock = 57,
+  F1 = 58,
+  F2 = 59,
+  F3 = 60,
+  F4 = 61,
+  F5 = 62,
+  F6 = 63,
+  F7 = 64,
+  F8 = 65,
+  F9 = 66,
+  F10 = 67,
+  F11 = 68,
+  F12 = 69,
+  PrintScreen = 70,
+  ScrollLock = 71,
+  Pause = 72,
+  Insert = 73,
+  Home = 74,
+  PageUp = 75,
+  Delete = 76,
+  End = 77,
+  PageDown = 78,
+  Right = 79,
+  Left = 80,
+  Down = 81,
+  Up = 82,
+  NumLockClear = 83,
+  KeypadDivide = 84,
+  KeypadMultiply = 85,
+  KeypadMinus = 86,
+  KeypadPlus = 87,
+  KeypadEnter = 88,
+  Keypad1 = 89,
+  Keypad2 = 90,
+  Keypad3 = 91,
+  Keypad4 = 92,
+  Keypad5 = 93,
+  Keypad6 = 94,
+  Keypad7 = 95,
+  Keypad8 = 96,
+  Keypad9 = 97,
+  Keypad0 = 98,
+  KeypadPeriod = 99,
+  NonUsBackslash = 100,
+  Application = 101,
+  Power = 102,
+  KeypadEquals = 103,
+  F13 = 104,
+  F14 = 105,
+  F15 = 106,
+  F16 = 107,
+  F17 = 108,
+  F18 = 109,
+  F19 = 110,
+  F20 = 111,
+  F21 = 112,
+  F22 = 113,
+  F23 = 114,
+  F24 = 115,
+  Execute = 116,
+  Help = 117,
+  Menu = 118,
+  Select = 119,
+  Stop = 120,
+  Again = 121,
+  Undo = 122,
+  Cut = 123,
+  Copy = 124,
+  Paste = 125,
+  Find = 126,
+  Mute = 127,
+  VolumeUp = 128,
+  VolumeDown = 129,
+  KeypadComma = 133,
+  KeypadEqualsAs400 = 134,
+  International1 = 135,
+  International2 = 136,
+  International3 = 137,
+  International4 = 138,
+  International5 = 139,
+  International6 = 140,
+  International7 = 141,
+  International8 = 142,
+  International9 = 143,
+  Lang1 = 144,
+  Lang2 = 145,
+  Lang3 = 146,
+  Lang4 = 147,
+  Lang5 = 148,
+  Lang6 = 149,
+  Lang7 = 150,
+  Lang8 = 151,
+  Lang9 = 152,
+  AltErase = 153,
+  SysReq = 154,
+  Cancel = 155,
+  Clear = 156,
+  Prior = 157,
+  Return2 = 158,
+  Separator = 159,
+  Out = 160,
+  Oper = 161,
+  ClearAgain = 162,
+  CrSel = 163,
+  ExSel = 164,
+  Keypad00 = 176,
+  Keypad000 = 177,
+  ThousandsSeparator = 178,
+  DecimalSeparator = 179,
+  CurrencyUnit = 180,
+  CurrencySubunit = 181,
+  KeypadLeftParen = 182,
+  KeypadRightParen = 183,
+  KeypadLeftBrace = 184,
+  KeypadRightBrace = 185,
+  KeypadTab = 186,
+  KeypadBackspace = 187,
+  KeypadA = 188,
+  KeypadB = 189,
+  KeypadC = 190,
+  KeypadD = 191,
+  KeypadE = 192,
+  KeypadF = 193,
+  KeypadXor = 194,
+  KeypadPower = 195,
+  KeypadPercent = 196,
+  KeypadLess = 197,
+  KeypadGreater = 198,
+  KeypadAmpersand = 199,
+  KeypadDblAmpersand = 200,
+  KeypadVerticalBar = 201,
+  KeypadDblVerticalBar = 202,
+  KeypadColon = 203,
+  KeypadHash = 204,
+  KeypadSpace = 205,
+  KeypadAt = 206,
+  KeypadExclam = 207,
+  KeypadMemStore = 208,
+  KeypadMemRecall = 209,
+  KeypadMemClear = 210,
+  KeypadMemAdd = 211,
+  KeypadMemSubtract = 212,
+  KeypadMemMultiply = 213,
+  KeypadMemDivide = 214,
+  KeypadPlusMinus = 215,
+  KeypadClear = 216,
+  KeypadClearEntry = 217,
+  KeypadBinary = 218,
+  KeypadOctal = 219,
+  KeypadDecimal = 220,
+  KeypadHexadecimal = 221,
+  LCtrl = 224,
+  LShift = 225,
+  LAlt = 226,
+  LGui = 227,
+  RCtrl = 228,
+  RShift = 229,
+  RAlt = 230,
+  RGui = 231,
+  Mode = 257,
+  AudioNext = 258,
+  AudioPrev = 259,
+  AudioStop = 260,
+  AudioPlay = 261,
+  AudioMute = 262,
+  MediaSelect = 263,
+  Www = 264,
+  Mail = 265,
+  Calculator = 266,
+  Computer = 267,
+  AcSearch = 268,
+  AcHome = 269,
+  AcBack = 270,
+  AcForward = 271,
+  AcStop = 272,
+  AcRefresh = 273,
+  AcBookmarks = 274,
+  BrightnessDown = 275,
+  BrightnessUp = 276,
+  DisplaySwitch = 277,
+  KbdIllumToggle = 278,
+  KbdIllumDown = 279,
+  KbdIllumUp = 280,
+  Eject = 281,
+  Sleep = 282,
+  App1 = 283,
+  App2 = 284,
+  AudioRewind = 285,
+  AudioFastForward = 286,
+}
+impl Scancode {
+  /// Converts from SDL's number for a scancode.
+  pub fn from_raw(raw: u32) -> Self {
+    match raw {
+      4 => Self::A,
+      5 => Self::B,
+      6 => Self::C,
+      7 => Self::D,
+      8 => Self::E,
+      9 => Self::F,
+      10 => Self::G,
+      11 => Self::H,
+      12 => Self::I,
+      13 => Self::J,
+      14 => Self::K,
+      15 => Self::L,
+      16 => Self::M,
+      17 => Self::N,
+      18 => Self::O,
+      19 => Self::P,
+      20 => Self::Q,
+      21 => Self::R,
+      22 => Self::S,
+      23 => Self::T,
+      24 => Self::U,
+      25 => Self::V,
+      26 => Self::W,
+      27 => Self::X,
+      28 => Self::Y,
+      29 => Self::Z,
+      30 => Self::Num1,
+      31 => Self::Num2,
+      32 => Self::Num3,
+      33 => Self::Num4,
+      34 => Self::Num5,
+      35 => Self::Num6,
+      36 => Self::Num7,
+      37 => Self::Num8,
+      38 => Self::Num9,
+      39 => Self::Num0,
+      40 => Self::Return,
+      41 => Self::Escape,
+      42 => Self::Backspace,
+      43 => Self::Tab,
+      44 => Self::Space,
+      45 => Self::Minus,
+      46 => Self::Equals,
+      47 => Self::LeftBracket,
+      48 => Self::RightBracket,
+      49 => Self::Backslash,
+      50 => Self::NonUsHash,
+      51 => Self::Semicolon,
+      52 => Self::Apostrophe,
+      53 => Self::Grave,
+      54 => Self::Comma,
+      55 => Self::Period,
+      56 => Self::Slash,
+      57 => Self::CapsLock,
+      58 => Self::F1,
+      59 => Self::F2,
+      60 => Self::F3,
+      61 => Self::F4,
+      62 => Self::F5,
+      63 => Self::F6,
+      64 => Self::F7,
+      65 => Self::F8,
+      66 => Self::F9,
+      67 => Self::F10,
+      68 => Self::F11,
+      69 => Self::F12,
+      70 => Self::PrintScreen,
+      71 => Self::ScrollLock,
+      72 => Self::Pause,
+      73 => Self::Insert,
+      74 => Self::Home,
+      75 => Self::PageUp,
+      76 => Self::Delete,
+      77 => Self::End,
+      78 => Self::PageDown,
+      79 => Self::Right,
+      80 => Self::Left,
+      81 => Self::Down,
+      82 => Self::Up,
+      83 => Self::NumLockClear,
+      84 => Self::KeypadDivide,
+      85 => Self::KeypadMultiply,
+      86 => Self::KeypadMinus,
+      87 => Self::KeypadPlus,
+      88 => Self::KeypadEnter,
+      89 => Self::Keypad1,
+      90 => Self::Keypad2,
+      91 => Self::Keypad3,
+      92 => Self::Keypad4,
+      93 => Self::Keypad5,
+      94 => Self::Keypad6,
+      95 => Self::Keypad7,
+      96 => Self::Keypad8,
+      97 => Self::Keypad9,
+      98 => Self::Keypad0,
+      99 => Self::KeypadPeriod,
+      100 => Self::NonUsBackslash,
+      101 => Self::Application,
+      102 => Self::Power,
+      103 => Self::KeypadEquals,
+      104 => Self::F13,
+      105 => Self::F14,
+      106 => Self::F15,
+      107 => Self::F16,
+      108 => Self::F17,
+      109 => Self::F18,
+      110 => Self::F19,
+      111 => Self::F20,
+      112 => Self::F21,
+      113 => Self::F22,
+      114 => Self::F23,
+      115 => Self::F24,
+      116 => Self::Execute,
+      117 => Self::Help,
+      118 => Self::Menu,
+      119 => Self::Select,
+      120 => Self::Stop,
+      121 => Self::Again,
+      122 => Self::Undo,
+      123 => Self::Cut,
+      124 => Self::Copy,
+      125 => Self::Paste,
+      126 => Self::Find,
+      127 => Self::Mute,
+      128 => Self::VolumeUp,
+      129 => Self::VolumeDown,
+      133 => Self::KeypadComma,
+      134 => Self::KeypadEqualsAs400,
+      135 => Self::International1,
+      136 => Self::International2,
+      137 => Self::International3,
+      138 => Self::International4,
+      139 => Self::International5,
+      140 => Self::International6,
+      141 => Self::International7,
+      142 => Self::International8,
+      143 => Self::International9,
+      144 => Self::Lang1,
+      145 => Self::Lang2,
+      146 => Self::Lang3,
+      147 => Self::Lang4,
+      148 => Self::Lang5,
+      149 => Self::Lang6,
+      150 => Self::Lang7,
+      151 => Self::Lang8,
+      152 => Self::Lang9,
+      153 => Self::AltErase,
+      154 => Self::SysReq,
+      155 => Self::Cancel,
+      156 => Self::Clear,
+      157 => Self::Prior,
+      158 => Self::Return2,
+      159 => Self::Separator,
+      160 => Self::Out,
+      161 => Self::Oper,
+      162 => Self::ClearAgain,
+      163 => Self::CrSel,
+      164 => Self::ExSel,
+      176 => Self::Keypad00,
+      177 => Self::Keypad000,
+      178 => Self::ThousandsSeparator,
+      179 => Self::DecimalSeparator,
+      180 => Self::CurrencyUnit,
+      181 => Self::CurrencySubunit,
+      182 => Self::KeypadLeftParen,
+      183 => Self::KeypadRightParen,
+      184 => Self::KeypadLeftBrace,
+      185 => Self::KeypadRightBrace,
+      186 => Self::KeypadTab,
+      187 => Self::KeypadBackspace,
+      188 => Self::KeypadA,
+      189 => Self::KeypadB,
+      190 => Self::KeypadC,
+      191 => Self::KeypadD,
+      192 => Self::KeypadE,
+      193 => Self::KeypadF,
+      194 => Self::KeypadXor,
+      195 => Self::KeypadPower,
+      196 => Self::KeypadPercent,
+      197 => Self::KeypadLess,
+      198 => Self::KeypadGreater,
+      199 => Self::KeypadAmpersand,
+      200 => Self::KeypadDblAmpersand,
+      201 => Self::KeypadVerticalBar,
+      202 => Self::KeypadDblVerticalBar,
+      203 => Self::KeypadColon,
+      204 => Self::KeypadHash,
+      205 => Self::KeypadSpace,
+      206 => Self::KeypadAt,
+      207 => Self::KeypadExclam,
+      208 => Self::KeypadMemStore,
+      209 => Self::KeypadMemRecall,
+      210 => Self::KeypadMemClear,
+      211 => Self::KeypadMemAdd,
+      212 => Self::KeypadMemSubtract,
+      213 => Self::KeypadMemMultiply,
+      214 => Self::KeypadMemDivide,
+      215 => Self::KeypadPlusMinus,
+      216 => Self::KeypadClear,
+      217 => Self::KeypadClearEntry,
+      218 => Self::KeypadBinary,
+      219 => Self::KeypadOctal,
+      220 => Self::KeypadDecimal,
+      221 => Self::KeypadHexadecimal,
+      224 => Self::LCtrl,
+      225 => Self::LShift,
+      226 => Self::LAlt,
+      227 => Self::LGui,
+      228 => Self::RCtrl,
+      229 => Self::RShift,
+      230 => Self::RAlt,
+      231 => Self::RGui,
+      257 => Self::Mode,
+      258 => Self::AudioNext,
+      259 => Self::AudioPrev,
+      260 => Self::AudioStop,
+      261 => Self::AudioPlay,
+      262 => Self::AudioMute,
+      263 => Self::MediaSelect,
+      264 => Self::Www,
+      265 => Self::Mail,
+      266 => Self::Calculator,
+      267 => Self::Computer,
+      268 => Self::AcSearch,
+      269 => Self::AcHome,
+      270 => Self::AcBack,
+      271 => Self::AcForward,
+      272 => Self::AcStop,
+      273 => Self::AcRefresh,
+      274 => Self::AcBookmarks,
+      275 => Self::BrightnessDown,
+      276 => Self::BrightnessUp,
+      277 => Self::DisplaySwitch,
+      278 => Self::KbdIllumToggle,
+      279 => Self::KbdIllumDown,
+      280 => Self::KbdIllumUp,
+      281 => Self::Eject,
+      282 => Self::Sleep,
+      283 => Self::App1,
+      284 => Self::App2,
+      285 => Self::AudioRewind,
+      286 => Self::AudioFastForward,
+      _ => Self::Unknown,
+    }
+  }
+
+  /// SDL's number for the scancode.
+  pub fn as_raw(self) -> u32 {
+    self as u32
+  }
+}
+
+/// A virtual key, which is what the key means in the current keyboard
+/// layout.
+///
+/// Use these for controls that are named by what they do (eg: `I` for
+/// inventory). Keys that make a character which isn't named here (such as
+/// `é`) come out as `Other`, holding SDL's keycode (the character's unicode
+/// value).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Keycode {
+  Unknown,
+  Return,
+  Escape,
+  Backspace,
+  Tab,
+  Space,
+  Exclaim,
+  QuoteDbl,
+  Hash,
+  Percent,
+  Dollar,
+  Ampersand,
+  Quote,
+  LeftParen,
+  RightParen,
+  Asterisk,
+  Plus,
+  Comma,
+  Minus,
+  Period,
+  Slash,
+  Num0,
+  Num1,
+  Num2,
+  Num3,
+  Num4,
+  Num5,
+  Num6,
+  Num7,
+  Num8,
+  Num9,
+  Colon,
+  Semicolon,
+  Less,
+  Equals,
+  Greater,
+  Question,
+  At,
+  LeftBracket,
+  Backslash,
+  RightBracket,
+  Caret,
+  Underscore,
+  Backquote,
+  A,
+  B,
+  C,
+  D,
+  E,
+  F,
+  G,
+  H,
+  I,
+  J,
+  K,
+  L,
+  M,
+  N,
+  O,
+  P,
+  Q,
+  R,
+  S,
+  T,
+  U,
+  V,
+  W,
+  X,
+  Y,
+  Z,
+  CapsLock,
+  F1,
+  F2,
+  F3,
+  F4,
+  F5,
+  F6,
+  F7,
+  F8,
+  F9,
+  F10,
+  F11,
+  F12,
+  PrintScreen,
+  ScrollLock,
+  Pause,
+  Insert,
+  Home,
+  PageUp,
+  Delete,
+  End,
+  PageDown,
+  Right,
+  Left,
+  Down,
+  Up,
+  NumLockClear,
+  KeypadDivide,
+  KeypadMultiply,
+  KeypadMinus,
+  KeypadPlus,
+  KeypadEnter,
+  Keypad1,
+  Keypad2,
+  Keypad3,
+  Keypad4,
+  Keypad5,
+  Keypad6,
+  Keypad7,
+  Keypad8,
+  Keypad9,
+  Keypad0,
+  KeypadPeriod,
+  Application,
+  Power,
+  KeypadEquals,
+  F13,
+  F14,
+  F15,
+  F16,
+  F17,
+  F18,
+  F19,
+  F20,
+  F21,
+  F22,
+  F23,
+  F24,
+  Execute,
+  Help,
+  Menu,
+  Select,
+  Stop,
+  Again,
+  Undo,
+  Cut,
+  Copy,
+  Paste,
+  Find,
+  Mute,
+  VolumeUp,
+  VolumeDown,
+  KeypadComma,
+  KeypadEqualsAs400,
+  AltErase,
+  SysReq,
+  Cancel,
+  Clear,
+  Prior,
+  Return2,
+  Separator,
+  Out,
+  Oper,
+  ClearAgain,
+  CrSel,
+  ExSel,
+  Keypad00,
+  Keypad000,
+  ThousandsSeparator,
+  DecimalSeparator,
+  CurrencyUnit,
+  CurrencySubunit,
+  KeypadLeftParen,
+  KeypadRightParen,
+  KeypadLeftBrace,
+  KeypadRightBrace,
+  KeypadTab,
+  KeypadBackspace,
+  KeypadA,
+  KeypadB,
+  KeypadC,
+  KeypadD,
+  KeypadE,
+  KeypadF,
+  KeypadXor,
+  KeypadPower,
+  KeypadPercent,
+  KeypadLess,
+  KeypadGreater,
+  KeypadAmpersand,
+  KeypadDblAmpersand,
+  KeypadVerticalBar,
+  KeypadDblVerticalBar,
+  KeypadColon,
+  KeypadHash,
+  KeypadSpace,
+  KeypadAt,
+  KeypadExclam,
+  KeypadMemStore,
+  KeypadMemRecall,
+  KeypadMemClear,
+  KeypadMemAdd,
+  KeypadMemSubtract,
+  KeypadMemMultiply,
+  KeypadMemDivide,
+  KeypadPlusMinus,
+  KeypadClear,
+  KeypadClearEntry,
+  KeypadBinary,
+  KeypadOctal,
+  KeypadDecimal,
+  KeypadHexadecimal,
+  LCtrl,
+  LShift,
+  LAlt,
+  LGui,
+  RCtrl,
+  RShift,
+  RAlt,
+  RGui,
+  Mode,
+  AudioNext,
+  AudioPrev,
+  AudioStop,
+  AudioPlay,
+  AudioMute,
+  MediaSelect,
+  Www,
+  Mail,
+  Calculator,
+  Computer,
+  AcSearch,
+  AcHome,
+  AcBack,
+  AcForward,
+  AcStop,
+  AcRefresh,
+  AcBookmarks,
+  BrightnessDown,
+  BrightnessUp,
+  DisplaySwitch,
+  KbdIllumToggle,
+  KbdIllumDown,
+  KbdIllumUp,
+  Eject,
+  Sleep,
+  App1,
+  App2,
+  AudioRewind,
+  AudioFastForward,
+  Other(i32),
+}
+impl Keycode {
+  /// Converts from SDL's number for a keycode.
+  pub fn from_raw(raw: i32) -> Self {
+    match raw {
+      13 => Self::Return,
+      27 => Self::Escape,
+      8 => Self::Backspace,
+      9 => Self::Tab,
+      32 => Self::Space,
+      33 => Self::Exclaim,
+      34 => Self::QuoteDbl,
+      35 => Self::Hash,
+      37 => Self::Percent,
+      36 => Self::Dollar,
+      38 => Self::Ampersand,
+      39 => Self::Quote,
+      40 => Self::LeftParen,
+      41 => Self::RightParen,
+      42 => Self::Asterisk,
+      43 => Self::Plus,
+      44 => Self::Comma,
+      45 => Self::Minus,
+      46 => Self::Period,
+      47 => Self::Slash,
+      48 => Self::Num0,
+      49 => Self::Num1,
+      50 => Self::Num2,
+      51 => Self::Num3,
+      52 => Self::Num4,
+      53 => Self::Num5,
+      54 => Self::Num6,
+      55 => Self::Num7,
+      56 => Self::Num8,
+      57 => Self::Num9,
+      58 => Self::Colon,
+      59 => Self::Semicolon,
+      60 => Self::Less,
+      61 => Self::Equals,
+      62 => Self::Greater,
+      63 => Self::Question,
+      64 => Self::At,
+      91 => Self::LeftBracket,
+      92 => Self::Backslash,
+      93 => Self::RightBracket,
+      94 => Self::Caret,
+      95 => Self::Underscore,
+      96 => Self::Backquote,
+      97 => Self::A,
+      98 => Self::B,
+      99 => Self::C,
+      100 => Self::D,
+      101 => Self::E,
+      102 => Self::F,
+      103 => Self::G,
+      104 => Self::H,
+      105 => Self::I,
+      106 => Self::J,
+      107 => Self::K,
+      108 => Self::L,
+      109 => Self::M,
+      110 => Self::N,
+      111 => Self::O,
+      112 => Self::P,
+      113 => Self::Q,
+      114 => Self::R,
+      115 => Self::S,
+      116 => Self::T,
+      117 => Self::U,
+      118 => Self::V,
+      119 => Self::W,
+      120 => Self::X,
+      121 => Self::Y,
+      122 => Self::Z,
+      1073741881 => Self::CapsLock,
+      1073741882 => Self::F1,
+      1073741883 => Self::F2,
+      1073741884 => Self::F3,
+      1073741885 => Self::F4,
+      1073741886 => Self::F5,
+      1073741887 => Self::F6,
+      1073741888 => Self::F7,
+      1073741889 => Self::F8,
+      1073741890 => Self::F9,
+      1073741891 => Self::F10,
+      1073741892 => Self::F11,
+      1073741893 => Self::F12,
+      1073741894 => Self::PrintScreen,
+      1073741895 => Self::ScrollLock,
+      1073741896 => Self::Pause,
+      1073741897 => Self::Insert,
+      1073741898 => Self::Home,
+      1073741899 => Self::PageUp,
+      127 => Self::Delete,
+      1073741901 => Self::End,
+      1073741902 => Self::PageDown,
+      1073741903 => Self::Right,
+      1073741904 => Self::Left,
+      1073741905 => Self::Down,
+      1073741906 => Self::Up,
+      1073741907 => Self::NumLockClear,
+      1073741908 => Self::KeypadDivide,
+      1073741909 => Self::KeypadMultiply,
+      1073741910 => Self::KeypadMinus,
+      1073741911 => Self::KeypadPlus,
+      1073741912 => Self::KeypadEnter,
+      1073741913 => Self::Keypad1,
+      1073741914 => Self::Keypad2,
+      1073741915 => Self::Keypad3,
+      1073741916 => Self::Keypad4,
+      1073741917 => Self::Keypad5,
+      1073741918 => Self::Keypad6,
+      1073741919 => Self::Keypad7,
+      1073741920 => Self::Keypad8,
+      1073741921 => Self::Keypad9,
+      1073741922 => Self::Keypad0,
+      1073741923 => Self::KeypadPeriod,
+      1073741925 => Self::Application,
+      1073741926 => Self::Power,
+      1073741927 => Self::KeypadEquals,
+      1073741928 => Self::F13,
+      1073741929 => Self::F14,
+      1073741930 => Self::F15,
+      1073741931 => Self::F16,
+      1073741932 => Self::F17,
+      1073741933 => Self::F18,
+      1073741934 => Self::F19,
+      1073741935 => Self::F20,
+      1073741936 => Self::F21,
+      1073741937 => Self::F22,
+      1073741938 => Self::F23,
+      1073741939 => Self::F24,
+      1073741940 => Self::Execute,
+      1073741941 => Self::Help,
+      1073741942 => Self::Menu,
+      1073741943 => Self::Select,
+      1073741944 => Self::Stop,
+      1073741945 => Self::Again,
+      1073741946 => Self::Undo,
+      1073741947 => Self::Cut,
+      1073741948 => Self::Copy,
+      1073741949 => Self::Paste,
+      1073741950 => Self::Find,
+      1073741951 => Self::Mute,
+      1073741952 => Self::VolumeUp,
+      1073741953 => Self::VolumeDown,
+      1073741957 => Self::KeypadComma,
+      1073741958 => Self::KeypadEqualsAs400,
+      1073741977 => Self::AltErase,
+      1073741978 => Self::SysReq,
+      1073741979 => Self::Cancel,
+      1073741980 => Self::Clear,
+      1073741981 => Self::Prior,
+      1073741982 => Self::Return2,
+      1073741983 => Self::Separator,
+      1073741984 => Self::Out,
+      1073741985 => Self::Oper,
+      1073741986 => Self::ClearAgain,
+      1073741987 => Self::CrSel,
+      1073741988 => Self::ExSel,
+      1073742000 => Self::Keypad00,
+      1073742001 => Self::Keypad000,
+      1073742002 => Self::ThousandsSeparator,
+      1073742003 => Self::DecimalSeparator,
+      1073742004 => Self::CurrencyUnit,
+      1073742005 => Self::CurrencySubunit,
+      1073742006 => Self::KeypadLeftParen,
+      1073742007 => Self::KeypadRightParen,
+      1073742008 => Self::KeypadLeftBrace,
+      1073742009 => Self::KeypadRightBrace,
+      1073742010 => Self::KeypadTab,
+      1073742011 => Self::KeypadBackspace,
+      1073742012 => Self::KeypadA,
+      1073742013 => Self::KeypadB,
+      1073742014 => Self::KeypadC,
+      1073742015 => Self::KeypadD,
+      1073742016 => Self::KeypadE,
+      1073742017 => Self::KeypadF,
+      1073742018 => Self::KeypadXor,
+      1073742019 => Self::KeypadPower,
+      1073742020 => Self::KeypadPercent,
+      1073742021 => Self::KeypadLess,
+      1073742022 => Self::KeypadGreater,
+      1073742023 => Self::KeypadAmpersand,
+      1073742024 => Self::KeypadDblAmpersand,
+      1073742025 => Self::KeypadVerticalBar,
+      1073742026 => Self::KeypadDblVerticalBar,
+      1073742027 => Self::KeypadColon,
+      1073742028 => Self::KeypadHash,
+      1073742029 => Self::KeypadSpace,
+      1073742030 => Self::KeypadAt,
+      1073742031 => Self::KeypadExclam,
+      1073742032 => Self::KeypadMemStore,
+      1073742033 => Self::KeypadMemRecall,
+      1073742034 => Self::KeypadMemClear,
+      1073742035 => Self::KeypadMemAdd,
+      1073742036 => Self::KeypadMemSubtract,
+      1073742037 => Self::KeypadMemMultiply,
+      1073742038 => Self::KeypadMemDivide,
+      1073742039 => Self::KeypadPlusMinus,
+      1073742040 => Self::KeypadClear,
+      1073742041 => Self::KeypadClearEntry,
+      1073742042 => Self::KeypadBinary,
+      1073742043 => Self::KeypadOctal,
+      1073742044 => Self::KeypadDecimal,
+      1073742045 => Self::KeypadHexadecimal,
+      1073742048 => Self::LCtrl,
+      1073742049 => Self::LShift,
+      1073742050 => Self::LAlt,
+      1073742051 => Self::LGui,
+      1073742052 => Self::RCtrl,
+      1073742053 => Self::RShift,
+      1073742054 => Self::RAlt,
+      1073742055 => Self::RGui,
+      1073742081 => Self::Mode,
+      1073742082 => Self::AudioNext,
+      1073742083 => Self::AudioPrev,
+      1073742084 => Self::AudioStop,
+      1073742085 => Self::AudioPlay,
+      1073742086 => Self::AudioMute,
+      1073742087 => Self::MediaSelect,
+      1073742088 => Self::Www,
+      1073742089 => Self::Mail,
+      1073742090 => Self::Calculator,
+      1073742091 => Self::Computer,
+      1073742092 => Self::AcSearch,
+      1073742093 => Self::AcHome,
+      1073742094 => Self::AcBack,
+      1073742095 => Self::AcForward,
+      1073742096 => Self::AcStop,
+      1073742097 => Self::AcRefresh,
+      1073742098 => Self::AcBookmarks,
+      1073742099 => Self::BrightnessDown,
+      1073742100 => Self::BrightnessUp,
+      1073742101 => Self::DisplaySwitch,
+      1073742102 => Self::KbdIllumToggle,
+      1073742103 => Self::KbdIllumDown,
+      1073742104 => Self::KbdIllumUp,
+      1073742105 => Self::Eject,
+      1073742106 => Self::Sleep,
+      1073742107 => Self::App1,
+      1073742108 => Self::App2,
+      1073742109 => Self::AudioRewind,
+      1073742110 => Self::AudioFastForward,
+      0 => Self::Unknown,
+      other => Self::Other(other),
+    }
+  }
+
+  /// SDL's number for the keycode.
+  pub fn as_raw(self) -> i32 {
+    match self {
+      Self::Unknown => 0,
+      Self::Return => 13,
+      Self::Escape => 27,
+      Self::Backspace => 8,
+      Self::Tab => 9,
+      Self::Space => 32,
+      Self::Exclaim => 33,
+      Self::QuoteDbl => 34,
+      Self::Hash => 35,
+      Self::Percent => 37,
+      Self::Dollar => 36,
+      Self::Ampersand => 38,
+      Self::Quote => 39,
+      Self::LeftParen => 40,
+      Self::RightParen => 41,
+      Self::Asterisk => 42,
+      Self::Plus => 43,
+      Self::Comma => 44,
+      Self::Minus => 45,
+      Self::Period => 46,
+      Self::Slash => 47,
+      Self::Num0 => 48,
+      Self::Num1 => 49,
+      Self::Num2 => 50,
+      Self::Num3 => 51,
+      Self::Num4 => 52,
+      Self::Num5 => 53,
+      Self::Num6 => 54,
+      Self::Num7 => 55,
+      Self::Num8 => 56,
+      Self::Num9 => 57,
+      Self::Colon => 58,
+      Self::Semicolon => 59,
+      Self::Less => 60,
+      Self::Equals => 61,
+      Self::Greater => 62,
+      Self::Question => 63,
+      Self::At => 64,
+      Self::LeftBracket => 91,
+      Self::Backslash => 92,
+      Self::RightBracket => 93,
+      Self::Caret => 94,
+      Self::Underscore => 95,
+      Self::Backquote => 96,
+      Self::A => 97,
+      Self::B => 98,
+      Self::C => 99,
+      Self::D => 100,
+      Self::E => 101,
+      Self::F => 102,
+      Self::G => 103,
+      Self::H => 104,
+      Self::I => 105,
+      Self::J => 106,
+      Self::K => 107,
+      Self::L => 108,
+      Self::M => 109,
+      Self::N => 110,
+      Self::O => 111,
+      Self::P => 112,
+      Self::Q => 113,
+      Self::R => 114,
+      Self::S => 115,
+      Self::T => 116,
+      Self::U => 117,
+      Self::V => 118,
+      Self::W => 119,
+      Self::X => 120,
+      Self::Y => 121,
+      Self::Z => 122,
+      Self::CapsLock => 1073741881,
+      Self::F1 => 1073741882,
+      Self::F2 => 1073741883,
+      Self::F3 => 1073741884,
+      Self::F4 => 1073741885,
+      Self::F5 => 1073741886,
+      Self::F6 => 1073741887,
+      Self::F7 => 1073741888,
+      Self::F8 => 1073741889,
+      Self::F9 => 1073741890,
+      Self::F10 => 1073741891,
+      Self::F11 => 1073741892,
+      Self::F12 => 1073741893,
+      Self::PrintScreen => 1073741894,
+      Self::ScrollLock => 1073741895,
+      Self::Pause => 1073741896,
+      Self::Insert => 1073741897,
+      Self::Home => 1073741898,
+      Self::PageUp => 1073741899,
+      Self::Delete => 127,
+      Self::End => 1073741901,
+      Self::PageDown => 1073741902,
+      Self::Right => 1073741903,
+      Self::Left => 1073741904,
+      Self::Down => 1073741905,
+      Self::Up => 1073741906,
+      Self::NumLockClear => 1073741907,
+      Self::KeypadDivide => 1073741908,
+      Self::KeypadMultiply => 1073741909,
+      Self::KeypadMinus => 1073741910,
+      Self::KeypadPlus => 1073741911,
+      Self::KeypadEnter => 1073741912,
+      Self::Keypad1 => 1073741913,
+      Self::Keypad2 => 1073741914,
+      Self::Keypad3 => 1073741915,
+      Self::Keypad4 => 1073741916,
+      Self::Keypad5 => 1073741917,
+      Self::Keypad6 => 1073741918,
+      Self::Keypad7 => 1073741919,
+      Self::Keypad8 => 1073741920,
+      Self::Keypad9 => 1073741921,
+      Self::Keypad0 => 1073741922,
+      Self::KeypadPeriod => 1073741923,
+      Self::Application => 1073741925,
+      Self::Power => 1073741926,
+      Self::KeypadEquals => 1073741927,
+      Self::F13 => 1073741928,
+      Self::F14 => 1073741929,
+      Self::F15 => 1073741930,
+      Self::F16 => 1073741931,
+      Self::F17 => 1073741932,
+      Self::F18 => 1073741933,
+      Self::F19 => 1073741934,
+      Self::F20 => 1073741935,
+      Self::F21 => 1073741936,
+      Self::F22 => 1073741937,
+      Self::F23 => 1073741938,
+      Self::F24 => 1073741939,
+      Self::Execute => 1073741940,
+      Self::Help => 1073741941,
+      Self::Menu => 1073741942,
+      Self::Select => 1073741943,
+      Self::Stop => 1073741944,
+      Self::Again => 1073741945,
+      Self::Undo => 1073741946,
+      Self::Cut => 1073741947,
+      Self::Copy => 1073741948,
+      Self::Paste => 1073741949,
+      Self::Find => 1073741950,
+      Self::Mute => 1073741951,
+      Self::VolumeUp => 1073741952,
+      Self::VolumeDown => 1073741953,
+      Self::KeypadComma => 1073741957,
+      Self::KeypadEqualsAs400 => 1073741958,
+      Self::AltErase => 1073741977,
+      Self::SysReq => 1073741978,
+      Self::Cancel => 1073741979,
+      Self::Clear => 1073741980,
+      Self::Prior => 1073741981,
+      Self::Return2 => 1073741982,
+      Self::Separator => 1073741983,
+      Self::Out => 1073741984,
+      Self::Oper => 1073741985,
+      Self::ClearAgain => 1073741986,
+      Self::CrSel => 1073741987,
+      Self::ExSel => 1073741988,
+      Self::Keypad00 => 1073742000,
+      Self::Keypad000 => 1073742001,
+      Self::ThousandsSeparator => 1073742002,
+      Self::DecimalSeparator => 1073742003,
+      Self::CurrencyUnit => 1073742004,
+      Self::CurrencySubunit => 1073742005,
+      Self::KeypadLeftParen => 1073742006,
+      Self::KeypadRightParen => 1073742007,
+      Self::KeypadLeftBrace => 1073742008,
+      Self::KeypadRightBrace => 1073742009,
+      Self::KeypadTab => 1073742010,
+      Self::KeypadBackspace => 1073742011,
+      Self::KeypadA => 1073742012,
+      Self::KeypadB => 1073742013,
+      Self::KeypadC => 1073742014,
+      Self::KeypadD => 1073742015,
+      Self::KeypadE => 1073742016,
+      Self::KeypadF => 1073742017,
+      Self::KeypadXor => 1073742018,
+      Self::KeypadPower => 1073742019,
+      Self::KeypadPercent => 1073742020,
+      Self::KeypadLess => 1073742021,
+      Self::KeypadGreater => 1073742022,
+      Self::KeypadAmpersand => 1073742023,
+      Self::KeypadDblAmpersand => 1073742024,
+      Self::KeypadVerticalBar => 1073742025,
+      Self::KeypadDblVerticalBar => 1073742026,
+      Self::KeypadColon => 1073742027,
+      Self::KeypadHash => 1073742028,
+      Self::KeypadSpace => 1073742029,
+      Self::KeypadAt => 1073742030,
+      Self::KeypadExclam => 1073742031,
+      Self::KeypadMemStore => 1073742032,
+      Self::KeypadMemRecall => 1073742033,
+      Self::KeypadMemClear => 1073742034,
+      Self::KeypadMemAdd => 1073742035,
+      Self::KeypadMemSubtract => 1073742036,
+      Self::KeypadMemMultiply => 1073742037,
+      Self::KeypadMemDivide => 1073742038,
+      Self::KeypadPlusMinus => 1073742039,
+      Self::KeypadClear => 1073742040,
+      Self::KeypadClearEntry => 1073742041,
+      Self::KeypadBinary => 1073742042,
+      Self::KeypadOctal => 1073742043,
+      Self::KeypadDecimal => 1073742044,
+      Self::KeypadHexadecimal => 1073742045,
+      Self::LCtrl => 1073742048,
+      Self::LShift => 1073742049,
+      Self::LAlt => 1073742050,
+      Self::LGui => 1073742051,
+      Self::RCtrl => 1073742052,
+      Self::RShift => 1073742053,
+      Self::RAlt => 1073742054,
+      Self::RGui => 1073742055,
+      Self::Mode => 1073742081,
+      Self::AudioNext => 1073742082,
+      Self::AudioPrev => 1073742083,
+      Self::AudioStop => 1073742084,
+      Self::AudioPlay => 1073742085,
+      Self::AudioMute => 1073742086,
+      Self::MediaSelect => 1073742087,
+      Self::Www => 1073742088,
+      Self::Mail => 1073742089,
+      Self::Calculator => 1073742090,
+      Self::Computer => 1073742091,
+      Self::AcSearch => 1073742092,
+      Self::AcHome => 1073742093,
+      Self::AcBack => 1073742094,
+      Self::AcForward => 1073742095,
+      Self::AcStop => 1073742096,
+      Self::AcRefresh => 1073742097,
+      Self::AcBookmarks => 1073742098,
+      Self::BrightnessDown => 1073742099,
+      Self::BrightnessUp => 1073742100,
+      Self::DisplaySwitch => 1073742101,
+      Self::KbdIllumToggle => 1073742102,
+      Self::KbdIllumDown => 1073742103,
+      Self::KbdIllumUp => 1073742104,
+      Self::Eject => 1073742105,
+      Self::Sleep => 1073742106,
+      Self::App1 => 1073742107,
+      Self::App2 => 1073742108,
+      Self::AudioRewind => 1073742109,
+      Self::AudioFastForward => 1073742110,
+      Self::Other(raw) => raw,
+    }
+  }
+}
+
+/// The modifier keys (and lock keys) that are held or active.
+///
+/// Combine these with `|`, and clear them with `& !`. The plain `SHIFT`,
+/// `CTRL`, `ALT`, and `GUI` values are both the left and right keys, so check
+/// them with `intersects`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct KeyModifiers(pub(crate) u16);
+#[allow(clippy::unnecessary_cast)]
+impl KeyModifiers {
+  pub const NONE: Self = Self(fermium::KMOD_NONE as u16);
+  pub const LSHIFT: Self = Self(fermium::KMOD_LSHIFT as u16);
+  pub const RSHIFT: Self = Self(fermium::KMOD_RSHIFT as u16);
+  pub const LCTRL: Self = Self(fermium::KMOD_LCTRL as u16);
+  pub const RCTRL: Self = Self(fermium::KMOD_RCTRL as u16);
+  pub const LALT: Self = Self(fermium::KMOD_LALT as u16);
+  pub const RALT: Self = Self(fermium::KMOD_RALT as u16);
+  pub const LGUI: Self = Self(fermium::KMOD_LGUI as u16);
+  pub const RGUI: Self = Self(fermium::KMOD_RGUI as u16);
+  pub const NUM_LOCK: Self = Self(fermium::KMOD_NUM as u16);
+  pub const CAPS_LOCK: Self = Self(fermium::KMOD_CAPS as u16);
+  /// The AltGr key.
+  pub const MODE: Self = Self(fermium::KMOD_MODE as u16);
+  pub const SHIFT: Self = Self(Self::LSHIFT.0 | Self::RSHIFT.0);
+  pub const CTRL: Self = Self(Self::LCTRL.0 | Self::RCTRL.0);
+  pub const ALT: Self = Self(Self::LALT.0 | Self::RALT.0);
+  pub const GUI: Self = Self(Self::LGUI.0 | Self::RGUI.0);
+}
+impl KeyModifiers {
+  /// If all of the modifiers in `other` are active.
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// If any of the modifiers in `other` are active.
+  pub const fn intersects(self, other: Self) -> bool {
+    self.0 & other.0 != 0
+  }
+
+  /// If either shift key is held.
+  pub const fn shift(self) -> bool {
+    self.intersects(Self::SHIFT)
+  }
+
+  /// If either ctrl key is held.
+  pub const fn ctrl(self) -> bool {
+    self.intersects(Self::CTRL)
+  }
+
+  /// If either alt key is held.
+  pub const fn alt(self) -> bool {
+    self.intersects(Self::ALT)
+  }
+
+  /// If either GUI key (Windows key, Command key) is held.
+  pub const fn gui(self) -> bool {
+    self.intersects(Self::GUI)
+  }
+
+  /// The raw SDL modifier bits.
+  pub const fn as_u16(self) -> u16 {
+    self.0
+  }
+}
+impl BitOr for KeyModifiers {
+  type Output = Self;
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+impl BitOrAssign for KeyModifiers {
+  fn bitor_assign(&mut self, rhs: Self) {
+    self.0 |= rhs.0
+  }
+}
+impl BitAnd for KeyModifiers {
+  type Output = Self;
+  fn bitand(self, rhs: Self) -> Self {
+    Self(self.0 & rhs.0)
+  }
+}
+impl BitAndAssign for KeyModifiers {
+  fn bitand_assign(&mut self, rhs: Self) {
+    self.0 &= rhs.0
+  }
+}
+impl Not for KeyModifiers {
+  type Output = Self;
+  fn not(self) -> Self {
+    Self(!self.0)
+  }
+}
+
+/// A copy of which keys were held, from [`Sdl::keyboard_state`](crate::Sdl::keyboard_state).
+///
+/// This doesn't change after you get it, so get a new one each frame.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyboardState {
+  pub(crate) keys: Vec<u8>,
+}
+impl KeyboardState {
+  /// If the key at this physical location was held.
+  pub fn is_scancode_pressed(&self, scancode: Scancode) -> bool {
+    matches!(self.keys.get(scancode.as_raw() as usize), Some(&key) if key != 0)
+  }
+
+  /// All of the keys that were held.
+  pub fn pressed_scancodes(&self) -> impl Iterator<Item = Scancode> + '_ {
+    self
+      .keys
+      .iter()
+      .enumerate()
+      .filter(|(_, &key)| key != 0)
+      .map(|(raw, _)| Scancode::from_raw(raw as u32))
+  }
+}
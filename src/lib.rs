@@ -2,6 +2,8 @@
 #![allow(unused_imports)]
 
 extern crate alloc;
+use core::convert::TryFrom;
+
 use alloc::{boxed::Box, string::String, vec::Vec};
 
 #[cfg(any(target_os = "macos", target_os = "ios", feature = "std"))]
@@ -13,6 +15,49 @@ pub use sdl::*;
 mod event;
 pub use event::*;
 
+mod user_event;
+pub use user_event::*;
+
+mod event_kind;
+pub use event_kind::*;
+
+mod event_filter;
+pub use event_filter::*;
+
+mod main_loop;
+pub use main_loop::*;
+
+#[cfg(feature = "std")]
+mod event_record;
+#[cfg(feature = "std")]
+pub use event_record::*;
+
+#[cfg(feature = "async")]
+mod event_stream;
+#[cfg(feature = "async")]
+pub use event_stream::*;
+
+mod keyboard;
+pub use keyboard::*;
+
+mod key_repeat;
+pub use key_repeat::*;
+
+mod text_composition;
+pub use text_composition::*;
+
+mod input_map;
+pub use input_map::*;
+
+mod input_state;
+pub use input_state::*;
+
+mod mouse;
+pub use mouse::*;
+
+mod touch;
+pub use touch::*;
+
 mod window;
 pub use window::*;
 
@@ -22,6 +67,15 @@ pub use renderer::*;
 mod surface;
 pub use surface::*;
 
+mod borrowed_surface;
+pub use borrowed_surface::*;
+
+mod yuv;
+pub use yuv::*;
+
+mod convert_pixels;
+pub use convert_pixels::*;
+
 mod pixel_format_enum;
 pub use pixel_format_enum::*;
 
@@ -31,15 +85,45 @@ pub use pixel_format::*;
 mod palette;
 pub use palette::*;
 
+mod blend_mode;
+pub use blend_mode::*;
+
 mod texture;
 pub use texture::*;
 
+mod render_commands;
+pub use render_commands::*;
+
+mod streaming_texture;
+pub use streaming_texture::*;
+
+mod debug_overlay;
+pub use debug_overlay::RenderStats;
+
 mod controller;
 pub use controller::*;
 
+mod controller_manager;
+pub use controller_manager::*;
+
+mod joystick;
+pub use joystick::*;
+
+mod haptic;
+pub use haptic::*;
+
 mod audio;
 pub use audio::*;
 
+mod rect;
+pub use rect::*;
+
+mod camera;
+pub use camera::*;
+
+mod tilemap;
+pub use tilemap::*;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct WindowID(u32);
@@ -55,12 +139,76 @@ impl MouseID {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
-pub struct MouseButtonState(u32);
+pub struct MouseButtonState(pub(crate) u32);
 
+/// The instance ID of a joystick or controller.
+///
+/// A device gets a new one each time it's plugged in, which then stays the
+/// same until it's unplugged. Events use this to say which device they're
+/// about.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct JoystickID(i32);
 
+/// Another name for [`JoystickID`], to go with [`JoystickDeviceIndex`].
+pub type JoystickInstanceId = JoystickID;
+
+/// The index of an attached joystick or controller, for opening it.
+///
+/// Indexes go from 0 up to [`Sdl::get_number_of_joysticks`], and they shift
+/// around as devices are plugged in and unplugged. Use one right away (such
+/// as from an `Added` event), then keep the device's [`JoystickInstanceId`]
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct JoystickDeviceIndex(pub usize);
+impl JoystickDeviceIndex {
+  /// The index as SDL takes it.
+  pub(crate) fn to_sdl(self) -> Result<i32, SdlError> {
+    i32::try_from(self.0).map_err(|_| {
+      SdlError(Box::new(String::from(
+        "beryllium: joystick device index out of range.",
+      )))
+    })
+  }
+}
+
+/// Identifies a kind of joystick (or controller), by its bus, vendor,
+/// product, and version.
+///
+/// This stays the same when the device is plugged back in, and between runs
+/// of the program. Two identical devices have the same GUID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct JoystickGuid(pub [u8; 16]);
+/// Formats as 32 lowercase hex digits, the same as SDL and mapping strings.
+impl core::fmt::Display for JoystickGuid {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    for byte in self.0.iter() {
+      write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+  }
+}
+/// Parses the 32 hex digits that `Display` gives.
+impl core::str::FromStr for JoystickGuid {
+  type Err = SdlError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let bytes = s.as_bytes();
+    let mut guid = [0_u8; 16];
+    if bytes.len() != 32 || !bytes.iter().all(u8::is_ascii_hexdigit) {
+      return Err(SdlError(Box::new(String::from(
+        "beryllium: a joystick GUID is 32 hex digits",
+      ))));
+    }
+    for (byte, pair) in guid.iter_mut().zip(bytes.chunks(2)) {
+      let pair = core::str::from_utf8(pair).unwrap();
+      *byte = u8::from_str_radix(pair, 16).unwrap();
+    }
+    Ok(Self(guid))
+  }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct AudioDeviceID(u32);
@@ -73,6 +221,48 @@ pub struct TouchID(i64);
 #[repr(transparent)]
 pub struct FingerID(i64);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct GestureID(i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SensorID(i32);
+
+/// When an event happened, in milliseconds since SDL was initialized.
+///
+/// SDL's clock wraps around after about 49 days, so use `since` to measure
+/// between two timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Timestamp(u32);
+impl Timestamp {
+  /// Makes a timestamp from milliseconds since SDL was initialized.
+  pub const fn from_millis(millis: u32) -> Self {
+    Self(millis)
+  }
+
+  /// The milliseconds since SDL was initialized.
+  pub const fn as_millis(self) -> u32 {
+    self.0
+  }
+
+  /// The time since SDL was initialized.
+  pub fn as_duration(self) -> core::time::Duration {
+    core::time::Duration::from_millis(u64::from(self.0))
+  }
+
+  /// The time from `earlier` until this timestamp.
+  pub fn since(self, earlier: Self) -> core::time::Duration {
+    core::time::Duration::from_millis(u64::from(self.0.wrapping_sub(earlier.0)))
+  }
+}
+impl core::fmt::Display for Timestamp {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "{}ms", self.0)
+  }
+}
+
 /// An error string from SDL.
 pub struct SdlError(
   // You  may not like it, but this is what peak performance looks like.
@@ -122,6 +312,52 @@ pub(crate) fn sdl_get_error() -> SdlError {
   }
 }
 
+/// Copies a null terminated string from SDL.
+///
+/// A null pointer gives an empty string, and any bad UTF-8 is replaced.
+pub(crate) unsafe fn string_from_sdl(mut p: *const u8) -> String {
+  let mut buf = Vec::new();
+  if !p.is_null() {
+    while *p != 0 {
+      buf.push(*p);
+      p = p.add(1);
+    }
+  }
+  match String::from_utf8(buf) {
+    Ok(s) => s,
+    Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+  }
+}
+
+/// Copies a null terminated string that SDL allocated, then frees it.
+///
+/// A null pointer gives `None`.
+pub(crate) unsafe fn take_string_from_sdl(p: *mut u8) -> Option<String> {
+  if p.is_null() {
+    None
+  } else {
+    let s = string_from_sdl(p);
+    fermium::SDL_free(p.cast());
+    Some(s)
+  }
+}
+
+/// Opens a file for SDL to read or write, `mode` being a C `fopen` mode.
+pub(crate) fn rw_from_file(
+  filename: &str, mode: &[u8],
+) -> Result<*mut fermium::SDL_RWops, SdlError> {
+  let filename_null: tinyvec::TinyVec<[u8; 64]> =
+    filename.as_bytes().iter().copied().chain(Some(0)).collect();
+  let rw_ops = unsafe {
+    fermium::SDL_RWFromFile(filename_null.as_ptr().cast(), mode.as_ptr().cast())
+  };
+  if rw_ops.is_null() {
+    Err(sdl_get_error())
+  } else {
+    Ok(rw_ops)
+  }
+}
+
 /// Gets the SDL version that the program is actually using.
 ///
 /// This *might* be a later version than the one you compiled against. However,
@@ -0,0 +1,172 @@
+use core::time::Duration;
+
+use crate::{Event, Sdl, Timestamp};
+
+/// Settings for [`Sdl::run`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MainLoopConfig {
+  /// How long each frame should take at least.
+  ///
+  /// * `None` runs frames as fast as possible, which is what you want if
+  ///   presenting is synced to vblank.
+  /// * On the web the browser always decides the frame rate.
+  pub frame_time: Option<Duration>,
+  /// If the loop should end after a [`Event::Quit`] has been handled.
+  pub exit_on_quit: bool,
+}
+impl Default for MainLoopConfig {
+  fn default() -> Self {
+    Self { frame_time: None, exit_on_quit: true }
+  }
+}
+
+/// One step of the main loop, given to the closure passed to [`Sdl::run`].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum MainLoopStep {
+  /// An event came in. All pending events are handed over before each tick.
+  Event(Event, Timestamp),
+  /// Update and draw a frame.
+  ///
+  /// `delta` is the time since the last tick (zero on the first one).
+  Tick { delta: Duration },
+}
+
+/// Lets the main loop closure look at and control the loop.
+#[derive(Debug)]
+pub struct MainLoopContext<'s> {
+  sdl: &'s Sdl,
+  frame: u64,
+  should_exit: bool,
+}
+impl<'s> MainLoopContext<'s> {
+  /// The SDL that's running the loop.
+  pub fn sdl(&self) -> &'s Sdl {
+    self.sdl
+  }
+
+  /// How many ticks have started, counting the current one.
+  pub fn frame(&self) -> u64 {
+    self.frame
+  }
+
+  /// Ends the loop once the current step returns.
+  pub fn exit(&mut self) {
+    self.should_exit = true;
+  }
+}
+
+/// The state that's carried between frames.
+struct MainLoop<'s, F> {
+  context: MainLoopContext<'s>,
+  config: MainLoopConfig,
+  f: F,
+  last_tick: Option<u64>,
+}
+impl<'s, F: FnMut(&mut MainLoopContext<'s>, MainLoopStep)> MainLoop<'s, F> {
+  /// Runs one frame. Gives `false` once the loop should end.
+  fn frame(&mut self) -> bool {
+    let sdl = self.context.sdl;
+    for (event, timestamp) in sdl.poll_events() {
+      let is_quit = event.is_quit();
+      (self.f)(&mut self.context, MainLoopStep::Event(event, timestamp));
+      if is_quit && self.config.exit_on_quit {
+        self.context.should_exit = true;
+      }
+      if self.context.should_exit {
+        return false;
+      }
+    }
+    let now = unsafe { fermium::SDL_GetPerformanceCounter() };
+    let delta = self.last_tick.map_or(Duration::from_secs(0), |last| {
+      counter_to_duration(now.wrapping_sub(last))
+    });
+    self.last_tick = Some(now);
+    self.context.frame += 1;
+    (self.f)(&mut self.context, MainLoopStep::Tick { delta });
+    !self.context.should_exit
+  }
+
+  /// Sleeps off whatever is left of the frame time.
+  #[cfg(not(target_os = "emscripten"))]
+  fn pace(&self) {
+    if let (Some(frame_time), Some(start)) =
+      (self.config.frame_time, self.last_tick)
+    {
+      let now = unsafe { fermium::SDL_GetPerformanceCounter() };
+      let spent = counter_to_duration(now.wrapping_sub(start));
+      if let Some(left) = frame_time.checked_sub(spent) {
+        // Round up, since SDL can only sleep in whole milliseconds.
+        let partial = left.subsec_nanos() % 1_000_000 != 0;
+        let millis = left.as_millis() + u128::from(partial);
+        let millis = millis.min(u128::from(u32::MAX)) as u32;
+        unsafe { fermium::SDL_Delay(millis) };
+      }
+    }
+  }
+}
+
+fn counter_to_duration(counts: u64) -> Duration {
+  let frequency = unsafe { fermium::SDL_GetPerformanceFrequency() }.max(1);
+  let secs = counts / frequency;
+  let nanos = (counts % frequency) * 1_000_000_000 / frequency;
+  Duration::new(secs, nanos as u32)
+}
+
+#[cfg(target_os = "emscripten")]
+extern "C" {
+  fn emscripten_set_main_loop_arg(
+    func: unsafe extern "C" fn(*mut core::ffi::c_void),
+    arg: *mut core::ffi::c_void, fps: i32, simulate_infinite_loop: i32,
+  );
+  fn emscripten_cancel_main_loop();
+}
+
+#[cfg(target_os = "emscripten")]
+unsafe extern "C" fn emscripten_frame<'s, F>(arg: *mut core::ffi::c_void)
+where
+  F: FnMut(&mut MainLoopContext<'s>, MainLoopStep),
+{
+  let main_loop = &mut *(arg as *mut MainLoop<'s, F>);
+  if !main_loop.frame() {
+    emscripten_cancel_main_loop();
+  }
+}
+
+impl Sdl {
+  /// Runs a main loop, calling `f` with each event and then once per frame.
+  ///
+  /// * Each frame, all pending events are given to `f`, then `f` gets a
+  ///   [`MainLoopStep::Tick`] where it should update and draw.
+  /// * Call [`MainLoopContext::exit`] to end the loop. By default the loop
+  ///   also ends after a quit event (see [`MainLoopConfig`]).
+  /// * On the web (emscripten) an endless loop would lock up the page, so
+  ///   frames are run by the browser instead. In that case this function never
+  ///   returns, and the program keeps going until the page is closed.
+  pub fn run<'s, F>(&'s self, config: MainLoopConfig, f: F)
+  where
+    F: FnMut(&mut MainLoopContext<'s>, MainLoopStep),
+  {
+    let mut main_loop = MainLoop {
+      context: MainLoopContext { sdl: self, frame: 0, should_exit: false },
+      config,
+      f,
+      last_tick: None,
+    };
+    #[cfg(target_os = "emscripten")]
+    unsafe {
+      // Zero fps means "use requestAnimationFrame". Simulating an infinite
+      // loop means this call never returns and our stack frame is kept, so
+      // the loop state stays alive.
+      emscripten_set_main_loop_arg(
+        emscripten_frame::<F>,
+        (&mut main_loop as *mut MainLoop<'s, F>).cast(),
+        0,
+        1,
+      );
+    }
+    #[cfg(not(target_os = "emscripten"))]
+    while main_loop.frame() {
+      main_loop.pace();
+    }
+  }
+}
@@ -0,0 +1,353 @@
+use core::{
+  ops::{BitOr, BitOrAssign},
+  ptr::NonNull,
+  time::Duration,
+};
+
+use alloc::{rc::Rc, sync::Arc};
+
+use fermium::{
+  SDL_Cursor, SDL_SystemCursor, SDL_SYSTEM_CURSOR_ARROW,
+  SDL_SYSTEM_CURSOR_CROSSHAIR, SDL_SYSTEM_CURSOR_HAND, SDL_SYSTEM_CURSOR_IBEAM,
+  SDL_SYSTEM_CURSOR_NO, SDL_SYSTEM_CURSOR_SIZEALL, SDL_SYSTEM_CURSOR_SIZENESW,
+  SDL_SYSTEM_CURSOR_SIZENS, SDL_SYSTEM_CURSOR_SIZENWSE,
+  SDL_SYSTEM_CURSOR_SIZEWE, SDL_SYSTEM_CURSOR_WAIT,
+  SDL_SYSTEM_CURSOR_WAITARROW,
+};
+
+use crate::{
+  sdl_get_error, Event, Initialization, MouseButtonState, SdlError, Surface,
+  Timestamp, Window,
+};
+
+/// A set of mouse buttons, such as the ones that are held down.
+///
+/// Combine these with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct MouseButtons(pub(crate) u32);
+impl MouseButtons {
+  pub const NONE: Self = Self(0);
+  pub const LEFT: Self = Self(1 << (fermium::SDL_BUTTON_LEFT - 1));
+  pub const MIDDLE: Self = Self(1 << (fermium::SDL_BUTTON_MIDDLE - 1));
+  pub const RIGHT: Self = Self(1 << (fermium::SDL_BUTTON_RIGHT - 1));
+  /// The first side button, usually "back".
+  pub const X1: Self = Self(1 << (fermium::SDL_BUTTON_X1 - 1));
+  /// The second side button, usually "forward".
+  pub const X2: Self = Self(1 << (fermium::SDL_BUTTON_X2 - 1));
+
+  /// If all of the buttons in `other` are in this set.
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// If no buttons are in this set.
+  pub const fn is_empty(self) -> bool {
+    self.0 == 0
+  }
+
+  /// The raw SDL button bits.
+  pub const fn as_u32(self) -> u32 {
+    self.0
+  }
+
+  /// The single button of a [`MouseButtonEvent`](crate::MouseButtonEvent),
+  /// which is a button number rather than a set.
+  pub(crate) fn from_button_number(button: MouseButtonState) -> Self {
+    match button.0 {
+      1..=32 => Self(1 << (button.0 - 1)),
+      _ => Self::NONE,
+    }
+  }
+}
+impl BitOr for MouseButtons {
+  type Output = Self;
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+impl BitOrAssign for MouseButtons {
+  fn bitor_assign(&mut self, rhs: Self) {
+    self.0 |= rhs.0
+  }
+}
+/// The `button_state` of a [`MouseMotionEvent`](crate::MouseMotionEvent) is
+/// the held buttons.
+impl From<MouseButtonState> for MouseButtons {
+  fn from(state: MouseButtonState) -> Self {
+    Self(state.0)
+  }
+}
+
+/// Where the mouse is, and which buttons are held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MouseState {
+  pub x_pos: i32,
+  pub y_pos: i32,
+  pub buttons: MouseButtons,
+}
+
+/// How far the mouse moved since the last check, and which buttons are held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct RelativeMouseState {
+  pub dx: i32,
+  pub dy: i32,
+  pub buttons: MouseButtons,
+}
+
+struct CursorInner {
+  nn: NonNull<SDL_Cursor>,
+  // Note(Lokathor): As long as the cursor lives, we have to also keep SDL
+  // itself alive.
+  #[allow(dead_code)]
+  init: Arc<Initialization>,
+}
+impl Drop for CursorInner {
+  // Note(Lokathor): The drop for the Arc runs *after* this drop code.
+  fn drop(&mut self) {
+    unsafe { fermium::SDL_FreeCursor(self.nn.as_ptr()) }
+  }
+}
+
+/// A mouse cursor image.
+///
+/// Make one with [`Sdl::create_color_cursor`](crate::Sdl::create_color_cursor)
+/// or [`Sdl::create_system_cursor`](crate::Sdl::create_system_cursor), and
+/// show it with [`Sdl::set_cursor`](crate::Sdl::set_cursor).
+///
+/// Clones share the same cursor. The cursor is kept alive while it's set,
+/// even if you drop all of your clones.
+#[derive(Clone)]
+pub struct Cursor(Rc<CursorInner>);
+impl core::fmt::Debug for Cursor {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "Cursor({:p})", self.0.nn)
+  }
+}
+impl PartialEq for Cursor {
+  fn eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.0, &other.0)
+  }
+}
+impl Eq for Cursor {}
+impl Cursor {
+  /// Makes a cursor from a surface, where `hotspot` is the pixel that's the
+  /// "tip" of the cursor.
+  pub(crate) fn from_surface(
+    init: Arc<Initialization>, surface: &Surface, [hot_x, hot_y]: [i32; 2],
+  ) -> Result<Self, SdlError> {
+    NonNull::new(unsafe {
+      fermium::SDL_CreateColorCursor(surface.nn.as_ptr(), hot_x, hot_y)
+    })
+    .ok_or_else(sdl_get_error)
+    .map(|nn| Self(Rc::new(CursorInner { nn, init })))
+  }
+
+  /// Makes one of the system's standard cursors.
+  pub(crate) fn system(
+    init: Arc<Initialization>, system_cursor: SystemCursor,
+  ) -> Result<Self, SdlError> {
+    NonNull::new(unsafe {
+      fermium::SDL_CreateSystemCursor(system_cursor as SDL_SystemCursor)
+    })
+    .ok_or_else(sdl_get_error)
+    .map(|nn| Self(Rc::new(CursorInner { nn, init })))
+  }
+
+  pub(crate) fn as_ptr(&self) -> *mut SDL_Cursor {
+    self.0.nn.as_ptr()
+  }
+}
+
+/// The standard cursors that the system provides.
+///
+/// Which image you actually get depends on the system, and some systems use
+/// the same image for more than one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SystemCursor {
+  /// The normal pointer.
+  Arrow = SDL_SYSTEM_CURSOR_ARROW as _,
+  /// For text that can be selected or edited.
+  IBeam = SDL_SYSTEM_CURSOR_IBEAM as _,
+  Wait = SDL_SYSTEM_CURSOR_WAIT as _,
+  Crosshair = SDL_SYSTEM_CURSOR_CROSSHAIR as _,
+  /// An arrow with a small wait symbol, for "busy, but you can still click".
+  WaitArrow = SDL_SYSTEM_CURSOR_WAITARROW as _,
+  /// Resizing from the top left or bottom right corner.
+  SizeNWSE = SDL_SYSTEM_CURSOR_SIZENWSE as _,
+  /// Resizing from the top right or bottom left corner.
+  SizeNESW = SDL_SYSTEM_CURSOR_SIZENESW as _,
+  /// Resizing from the left or right edge.
+  SizeWE = SDL_SYSTEM_CURSOR_SIZEWE as _,
+  /// Resizing from the top or bottom edge.
+  SizeNS = SDL_SYSTEM_CURSOR_SIZENS as _,
+  /// Moving something in any direction.
+  SizeAll = SDL_SYSTEM_CURSOR_SIZEALL as _,
+  /// A "not allowed" sign.
+  No = SDL_SYSTEM_CURSOR_NO as _,
+  /// A pointing hand, for links.
+  Hand = SDL_SYSTEM_CURSOR_HAND as _,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct LastClick {
+  button: MouseButtonState,
+  timestamp: Timestamp,
+  x_pos: i32,
+  y_pos: i32,
+  count: u32,
+}
+
+/// Counts clicks in a row (double clicks, triple clicks, and so on) with your
+/// own timing.
+///
+/// A press counts as part of a run if it's the same button, within
+/// `interval` of the last press, and close to where the last press was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClickCounter {
+  interval: Duration,
+  max_distance: u32,
+  last: Option<LastClick>,
+}
+impl ClickCounter {
+  /// Makes a counter with a max distance of 4 pixels.
+  pub fn new(interval: Duration) -> Self {
+    Self { interval, max_distance: 4, last: None }
+  }
+
+  /// Sets how far (in pixels, on either axis) the mouse can move between the
+  /// clicks of a run.
+  pub fn set_max_distance(&mut self, max_distance: u32) {
+    self.max_distance = max_distance;
+  }
+
+  /// Checks an event, giving the click count if it was a button press.
+  pub fn handle_event(
+    &mut self, event: &Event, timestamp: Timestamp,
+  ) -> Option<u32> {
+    let mouse_button = match event {
+      Event::MouseButton(mouse_button) if mouse_button.is_pressed => {
+        mouse_button
+      }
+      _ => return None,
+    };
+    let near = |a: i32, b: i32| {
+      (i64::from(a) - i64::from(b)).abs() <= i64::from(self.max_distance)
+    };
+    let count = match self.last {
+      Some(last)
+        if last.button == mouse_button.button
+          && timestamp.since(last.timestamp) <= self.interval
+          && near(mouse_button.x_pos, last.x_pos)
+          && near(mouse_button.y_pos, last.y_pos) =>
+      {
+        last.count.saturating_add(1)
+      }
+      _ => 1,
+    };
+    self.last = Some(LastClick {
+      button: mouse_button.button,
+      timestamp,
+      x_pos: mouse_button.x_pos,
+      y_pos: mouse_button.y_pos,
+      count,
+    });
+    Some(count)
+  }
+
+  /// Forgets the last click, so the next one starts a new run.
+  pub fn reset(&mut self) {
+    self.last = None;
+  }
+}
+
+/// How the mouse behaves over a window, see
+/// [`Window::set_mouse_mode`](crate::Window::set_mouse_mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MouseMode {
+  /// The normal mode: the cursor is shown and can leave the window.
+  Absolute,
+  /// The cursor is shown, but can't leave the window.
+  Confined,
+  /// The cursor is hidden and held in place, and only motion is reported.
+  ///
+  /// This is for first person cameras and the like.
+  Relative,
+}
+
+/// Keeps a [`MouseMode`] set, putting the old settings back when dropped.
+///
+/// Make one with [`Window::set_mouse_mode`](crate::Window::set_mouse_mode).
+pub struct MouseModeGuard<'w> {
+  win: &'w Window,
+  old_grab: bool,
+  old_relative: bool,
+  old_visible: bool,
+}
+impl core::fmt::Debug for MouseModeGuard<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "MouseModeGuard({:p})", self.win.as_ptr())
+  }
+}
+impl Drop for MouseModeGuard<'_> {
+  fn drop(&mut self) {
+    // Note: Leave relative mode first, since SDL restores the
+    // cursor as it does so.
+    unsafe {
+      fermium::SDL_SetRelativeMouseMode(sdl_bool(self.old_relative));
+      fermium::SDL_SetWindowGrab(self.win.as_ptr(), sdl_bool(self.old_grab));
+      fermium::SDL_ShowCursor(if self.old_visible {
+        fermium::SDL_ENABLE as i32
+      } else {
+        fermium::SDL_DISABLE as i32
+      });
+    }
+  }
+}
+impl<'w> MouseModeGuard<'w> {
+  pub(crate) fn new(
+    win: &'w Window, mode: MouseMode,
+  ) -> Result<Self, SdlError> {
+    let guard = unsafe {
+      Self {
+        win,
+        old_grab: fermium::SDL_GetWindowGrab(win.as_ptr()) == fermium::SDL_TRUE,
+        old_relative: fermium::SDL_GetRelativeMouseMode() == fermium::SDL_TRUE,
+        old_visible: fermium::SDL_ShowCursor(fermium::SDL_QUERY)
+          == fermium::SDL_ENABLE as i32,
+      }
+    };
+    let (grab, relative, visible) = match mode {
+      MouseMode::Absolute => (false, false, true),
+      MouseMode::Confined => (true, false, true),
+      MouseMode::Relative => (true, true, false),
+    };
+    unsafe {
+      fermium::SDL_ShowCursor(if visible {
+        fermium::SDL_ENABLE as i32
+      } else {
+        fermium::SDL_DISABLE as i32
+      });
+      fermium::SDL_SetWindowGrab(win.as_ptr(), sdl_bool(grab));
+      if fermium::SDL_SetRelativeMouseMode(sdl_bool(relative)) < 0 {
+        // Note: The guard's drop puts the other settings back.
+        return Err(sdl_get_error());
+      }
+    }
+    Ok(guard)
+  }
+
+  /// The window that the mode is set for.
+  pub fn window(&self) -> &'w Window {
+    self.win
+  }
+}
+
+fn sdl_bool(b: bool) -> fermium::SDL_bool {
+  if b {
+    fermium::SDL_TRUE
+  } else {
+    fermium::SDL_FALSE
+  }
+}
@@ -10,7 +10,7 @@ use crate::{sdl_get_error, SdlError};
 /// automatically created as necessary as part of allocating a new PixelFormat.
 #[repr(transparent)]
 pub struct Palette {
-  nn: NonNull<SDL_Palette>,
+  pub(crate) nn: NonNull<SDL_Palette>,
 }
 impl Drop for Palette {
   fn drop(&mut self) {
@@ -26,6 +26,13 @@ impl Palette {
     .map(|nn| Palette { nn })
   }
 
+  /// Makes a new palette holding exactly the given colors.
+  pub fn from_colors(colors: &[[u8; 4]]) -> Result<Self, SdlError> {
+    let palette = Self::new(colors.len())?;
+    palette.set_colors(colors, 0)?;
+    Ok(palette)
+  }
+
   pub fn len(&self) -> usize {
     unsafe { (*self.nn.as_ptr()).ncolors as usize }
   }
@@ -32,9 +32,11 @@ impl PixelFormat {
   }
 
   pub fn palette(&self) -> &Option<Palette> {
+    // Note: `Palette` is a transparent `NonNull`, so the palette pointer field
+    // itself can be viewed as an `Option<Palette>`.
     unsafe {
-      let p: *const SDL_Palette = (*self.nn.as_ptr()).palette;
-      core::mem::transmute::<&*const SDL_Palette, &Option<Palette>>(&p)
+      let p: *const *mut SDL_Palette = &(*self.nn.as_ptr()).palette;
+      &*p.cast::<Option<Palette>>()
     }
   }
 
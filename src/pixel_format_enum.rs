@@ -1,3 +1,5 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
 pub struct PixelFormatEnum(pub(crate) u32);
 impl PixelFormatEnum {
   pub const INDEX1LSB: Self = Self(fermium::SDL_PIXELFORMAT_INDEX1LSB as u32);
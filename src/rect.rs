@@ -0,0 +1,79 @@
+use fermium::{SDL_FRect, SDL_Rect};
+
+/// A rectangle with integer position and size.
+///
+/// The position is the upper left corner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rect {
+  pub x: i32,
+  pub y: i32,
+  pub w: u32,
+  pub h: u32,
+}
+impl Rect {
+  pub const fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+    Self { x, y, w, h }
+  }
+
+  /// If the two rectangles overlap at all.
+  ///
+  /// Rectangles that only share an edge don't count as overlapping.
+  pub fn intersects(self, other: Self) -> bool {
+    let (ax2, ay2) =
+      (self.x as i64 + self.w as i64, self.y as i64 + self.h as i64);
+    let (bx2, by2) =
+      (other.x as i64 + other.w as i64, other.y as i64 + other.h as i64);
+    (self.x as i64) < bx2
+      && (other.x as i64) < ax2
+      && (self.y as i64) < by2
+      && (other.y as i64) < ay2
+  }
+}
+impl From<Rect> for SDL_Rect {
+  #[inline]
+  fn from(r: Rect) -> Self {
+    SDL_Rect { x: r.x, y: r.y, w: r.w as i32, h: r.h as i32 }
+  }
+}
+impl From<SDL_Rect> for Rect {
+  #[inline]
+  fn from(r: SDL_Rect) -> Self {
+    Rect { x: r.x, y: r.y, w: r.w.max(0) as u32, h: r.h.max(0) as u32 }
+  }
+}
+
+/// A rectangle with floating point position and size.
+///
+/// The position is the upper left corner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct RectF {
+  pub x: f32,
+  pub y: f32,
+  pub w: f32,
+  pub h: f32,
+}
+impl RectF {
+  pub const fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+    Self { x, y, w, h }
+  }
+}
+impl From<RectF> for SDL_FRect {
+  #[inline]
+  fn from(r: RectF) -> Self {
+    SDL_FRect { x: r.x, y: r.y, w: r.w, h: r.h }
+  }
+}
+impl From<Rect> for RectF {
+  #[inline]
+  fn from(r: Rect) -> Self {
+    RectF { x: r.x as f32, y: r.y as f32, w: r.w as f32, h: r.h as f32 }
+  }
+}
+
+/// Pointer to the value, or null.
+pub(crate) fn opt_ptr<T>(opt: &Option<T>) -> *const T {
+  match opt.as_ref() {
+    Some(r) => r,
+    None => core::ptr::null(),
+  }
+}
@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+
+use crate::{FlipMode, Rect, RectF, RendererWindow, SdlError, Texture};
+
+/// A single recorded drawing operation.
+///
+/// These mirror the drawing methods of [`RendererWindow`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderCommand<'t> {
+  SetDrawColor([u8; 4]),
+  Clear,
+  Copy {
+    texture: &'t Texture,
+    src: Option<Rect>,
+    dst: Option<RectF>,
+    angle: f64,
+    flip: FlipMode,
+  },
+  DrawPoint([f32; 2]),
+  DrawLine([f32; 2], [f32; 2]),
+  DrawRect(RectF),
+  FillRect(RectF),
+}
+
+/// A list of drawing commands that can be built up now and drawn later.
+///
+/// * Build a frame (or part of one) in any order, then
+///   [`replay`](Self::replay) it onto a renderer.
+/// * The same buffer can be replayed any number of times, and the recorded
+///   commands can be inspected with [`commands`](Self::commands), which is
+///   handy for debugging a single frame.
+/// * [`sort_by_texture`](Self::sort_by_texture) groups copies from the same
+///   texture together to cut down on GPU state changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderCommandBuffer<'t> {
+  commands: Vec<RenderCommand<'t>>,
+}
+impl<'t> RenderCommandBuffer<'t> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The commands recorded so far, in replay order.
+  pub fn commands(&self) -> &[RenderCommand<'t>] {
+    &self.commands
+  }
+
+  /// Removes all recorded commands.
+  pub fn reset(&mut self) {
+    self.commands.clear()
+  }
+
+  /// Records any command.
+  pub fn push(&mut self, command: RenderCommand<'t>) {
+    self.commands.push(command)
+  }
+
+  pub fn set_draw_color(&mut self, rgba: [u8; 4]) {
+    self.push(RenderCommand::SetDrawColor(rgba))
+  }
+
+  pub fn clear(&mut self) {
+    self.push(RenderCommand::Clear)
+  }
+
+  pub fn copy(
+    &mut self, texture: &'t Texture, src: Option<Rect>, dst: Option<RectF>,
+  ) {
+    self.copy_ex(texture, src, dst, 0.0, FlipMode::None)
+  }
+
+  pub fn copy_ex(
+    &mut self, texture: &'t Texture, src: Option<Rect>, dst: Option<RectF>,
+    angle: f64, flip: FlipMode,
+  ) {
+    self.push(RenderCommand::Copy { texture, src, dst, angle, flip })
+  }
+
+  pub fn draw_point(&mut self, p: [f32; 2]) {
+    self.push(RenderCommand::DrawPoint(p))
+  }
+
+  pub fn draw_line(&mut self, a: [f32; 2], b: [f32; 2]) {
+    self.push(RenderCommand::DrawLine(a, b))
+  }
+
+  pub fn draw_rect(&mut self, r: RectF) {
+    self.push(RenderCommand::DrawRect(r))
+  }
+
+  pub fn fill_rect(&mut self, r: RectF) {
+    self.push(RenderCommand::FillRect(r))
+  }
+
+  /// Reorders copies so that copies from the same texture are next to each
+  /// other.
+  ///
+  /// * Only runs of back-to-back copies are reordered. Copies never move past
+  ///   any other kind of command, so draw color changes and primitives stay
+  ///   where they were.
+  /// * Within a run, copies of the same texture keep their relative order.
+  /// * This changes which copy ends up on top when copies from *different*
+  ///   textures overlap, so only use it when that doesn't matter.
+  pub fn sort_by_texture(&mut self) {
+    fn texture_key(command: &RenderCommand<'_>) -> usize {
+      match command {
+        RenderCommand::Copy { texture, .. } => texture.nn.as_ptr() as usize,
+        _ => 0,
+      }
+    }
+    let is_copy =
+      |c: &RenderCommand<'_>| matches!(c, RenderCommand::Copy { .. });
+    let mut start = 0;
+    while start < self.commands.len() {
+      if !is_copy(&self.commands[start]) {
+        start += 1;
+        continue;
+      }
+      let mut end = start;
+      while end < self.commands.len() && is_copy(&self.commands[end]) {
+        end += 1;
+      }
+      self.commands[start..end].sort_by_key(texture_key);
+      start = end;
+    }
+  }
+
+  /// Performs every recorded command on the renderer, in order.
+  ///
+  /// Stops at the first command that fails.
+  pub fn replay(&self, rend_win: &RendererWindow) -> Result<(), SdlError> {
+    for command in self.commands.iter() {
+      match *command {
+        RenderCommand::SetDrawColor(rgba) => rend_win.set_draw_color(rgba)?,
+        RenderCommand::Clear => rend_win.clear()?,
+        RenderCommand::Copy { texture, src, dst, angle, flip } => {
+          rend_win.copy_ex_f(texture, src, dst, angle, flip)?
+        }
+        RenderCommand::DrawPoint(p) => rend_win.draw_point_f(p)?,
+        RenderCommand::DrawLine(a, b) => rend_win.draw_line_f(a, b)?,
+        RenderCommand::DrawRect(r) => rend_win.draw_rect_f(r)?,
+        RenderCommand::FillRect(r) => rend_win.fill_rect_f(r)?,
+      }
+    }
+    Ok(())
+  }
+}
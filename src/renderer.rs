@@ -1,12 +1,14 @@
-use core::{convert::TryInto, ops::Deref, ptr::NonNull};
+use core::{convert::TryInto, ffi::c_void, ops::Deref, ptr::NonNull};
 
 use alloc::{rc::Rc, sync::Arc};
 
-use fermium::SDL_Renderer;
+use fermium::{SDL_FRect, SDL_Rect, SDL_Renderer};
 
 use crate::{
-  sdl_get_error, Initialization, PixelFormatEnum, SdlError, Surface, Texture,
-  Window, WindowCreationFlags,
+  debug_overlay::{texture_bytes, RenderCounters},
+  rect::opt_ptr,
+  sdl_get_error, Initialization, PixelFormatEnum, Rect, RectF, RenderStats,
+  SdlError, Surface, Texture, Window, WindowCreationFlags,
 };
 
 pub(crate) struct Renderer {
@@ -15,6 +17,7 @@ pub(crate) struct Renderer {
   // window that created it alive.
   #[allow(dead_code)]
   win: Rc<Window>,
+  pub(crate) counters: RenderCounters,
 }
 impl Drop for Renderer {
   // Note(Lokathor): The drop for the Rc runs *after* this drop code.
@@ -52,7 +55,11 @@ impl RendererWindow {
       )
     })
     .ok_or_else(sdl_get_error)?;
-    let rend = Rc::new(Renderer { nn, win: win.clone() });
+    let rend = Rc::new(Renderer {
+      nn,
+      win: win.clone(),
+      counters: RenderCounters::default(),
+    });
     Ok(RendererWindow { win, rend })
   }
 
@@ -66,9 +73,257 @@ impl RendererWindow {
   }
 
   pub fn present(&self) {
+    self.rend.counters.end_frame(self.rend.nn.as_ptr());
     unsafe { fermium::SDL_RenderPresent(self.rend.nn.as_ptr()) }
   }
 
+  /// Turns the debug performance overlay on or off.
+  ///
+  /// When it's on, each call to [`present`](Self::present) first draws a small
+  /// panel in the upper left of the target showing:
+  /// * A graph of recent frame times (the yellow line is 60fps).
+  /// * A blue bar, one pixel per draw call in the last frame.
+  /// * An orange bar, one pixel per MiB of estimated texture memory.
+  ///
+  /// The same numbers are available from [`render_stats`](Self::render_stats)
+  /// whether or not the overlay is on.
+  pub fn set_debug_overlay(&self, enabled: bool) {
+    self.rend.counters.set_overlay_enabled(enabled)
+  }
+
+  /// If the debug performance overlay is on.
+  pub fn debug_overlay(&self) -> bool {
+    self.rend.counters.overlay_enabled()
+  }
+
+  /// Gets the renderer's frame time, draw call, and texture memory counters.
+  pub fn render_stats(&self) -> RenderStats {
+    self.rend.counters.stats()
+  }
+
+  /// Sets the color used by `clear` and by the primitive drawing methods.
+  pub fn set_draw_color(&self, [r, g, b, a]: [u8; 4]) -> Result<(), SdlError> {
+    let ret = unsafe {
+      fermium::SDL_SetRenderDrawColor(self.rend.nn.as_ptr(), r, g, b, a)
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Gets the size of the rendering area in physical pixels.
+  ///
+  /// This ignores any logical size that's been set.
+  pub fn output_size(&self) -> Result<[u32; 2], SdlError> {
+    let mut w = 0;
+    let mut h = 0;
+    let ret = unsafe {
+      fermium::SDL_GetRendererOutputSize(self.rend.nn.as_ptr(), &mut w, &mut h)
+    };
+    if ret >= 0 {
+      Ok([w as u32, h as u32])
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Gets the device independent resolution, if one is set.
+  pub fn logical_size(&self) -> Option<[u32; 2]> {
+    let mut w = 0;
+    let mut h = 0;
+    unsafe {
+      fermium::SDL_RenderGetLogicalSize(self.rend.nn.as_ptr(), &mut w, &mut h)
+    };
+    if w > 0 && h > 0 {
+      Some([w as u32, h as u32])
+    } else {
+      None
+    }
+  }
+
+  /// Sets a device independent resolution for rendering.
+  ///
+  /// All drawing is scaled (and letterboxed as necessary) to fit the real
+  /// output, and mouse events are scaled back into the logical space. Pass
+  /// `None` to go back to drawing in physical pixels.
+  pub fn set_logical_size(
+    &self, size: Option<[u32; 2]>,
+  ) -> Result<(), SdlError> {
+    let [w, h] = size.unwrap_or([0, 0]);
+    let ret = unsafe {
+      fermium::SDL_RenderSetLogicalSize(
+        self.rend.nn.as_ptr(),
+        w.try_into().unwrap(),
+        h.try_into().unwrap(),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Copies all or part of a texture into the rendering target.
+  ///
+  /// * `src` of `None` uses the whole texture.
+  /// * `dst` of `None` fills the whole target.
+  pub fn copy(
+    &self, texture: &Texture, src: Option<Rect>, dst: Option<Rect>,
+  ) -> Result<(), SdlError> {
+    let src: Option<SDL_Rect> = src.map(Into::into);
+    let dst: Option<SDL_Rect> = dst.map(Into::into);
+    self.rend.counters.count_draw_call();
+    let ret = unsafe {
+      fermium::SDL_RenderCopy(
+        self.rend.nn.as_ptr(),
+        texture.nn.as_ptr(),
+        opt_ptr(&src),
+        opt_ptr(&dst),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// As [`copy`](Self::copy), but with a sub-pixel destination, a rotation
+  /// (in degrees, clockwise) around the center of `dst`, and optional
+  /// flipping.
+  pub fn copy_ex_f(
+    &self, texture: &Texture, src: Option<Rect>, dst: Option<RectF>,
+    angle: f64, flip: FlipMode,
+  ) -> Result<(), SdlError> {
+    let src: Option<SDL_Rect> = src.map(Into::into);
+    let dst: Option<SDL_FRect> = dst.map(Into::into);
+    self.rend.counters.count_draw_call();
+    let ret = unsafe {
+      fermium::SDL_RenderCopyExF(
+        self.rend.nn.as_ptr(),
+        texture.nn.as_ptr(),
+        opt_ptr(&src),
+        opt_ptr(&dst),
+        angle,
+        core::ptr::null(),
+        flip as _,
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Draws a single point with the current draw color.
+  pub fn draw_point_f(&self, [x, y]: [f32; 2]) -> Result<(), SdlError> {
+    self.rend.counters.count_draw_call();
+    let ret =
+      unsafe { fermium::SDL_RenderDrawPointF(self.rend.nn.as_ptr(), x, y) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Draws a line with the current draw color.
+  pub fn draw_line_f(
+    &self, [x1, y1]: [f32; 2], [x2, y2]: [f32; 2],
+  ) -> Result<(), SdlError> {
+    self.rend.counters.count_draw_call();
+    let ret = unsafe {
+      fermium::SDL_RenderDrawLineF(self.rend.nn.as_ptr(), x1, y1, x2, y2)
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Draws a connected series of lines with the current draw color.
+  pub fn draw_lines_f(&self, points: &[[f32; 2]]) -> Result<(), SdlError> {
+    self.rend.counters.count_draw_call();
+    let ret = unsafe {
+      fermium::SDL_RenderDrawLinesF(
+        self.rend.nn.as_ptr(),
+        points.as_ptr().cast(),
+        points.len().try_into().unwrap(),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Draws a rectangle outline with the current draw color.
+  pub fn draw_rect_f(&self, rect: RectF) -> Result<(), SdlError> {
+    let rect: SDL_FRect = rect.into();
+    self.rend.counters.count_draw_call();
+    let ret =
+      unsafe { fermium::SDL_RenderDrawRectF(self.rend.nn.as_ptr(), &rect) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Fills a rectangle with the current draw color.
+  pub fn fill_rect_f(&self, rect: RectF) -> Result<(), SdlError> {
+    let rect: SDL_FRect = rect.into();
+    self.rend.counters.count_draw_call();
+    let ret =
+      unsafe { fermium::SDL_RenderFillRectF(self.rend.nn.as_ptr(), &rect) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Reads the current render target's pixels back into a new surface.
+  ///
+  /// * The surface is the renderer's output size and is in
+  ///   [`PixelFormatEnum::ARGB8888`].
+  /// * Do this after drawing and *before* [`present`](Self::present), since
+  ///   the back buffer's content is undefined after presenting.
+  /// * This is slow! It's meant for screenshots and tests, not every frame.
+  pub fn read_pixels(&self) -> Result<Surface, SdlError> {
+    let [w, h] = self.output_size()?;
+    let format = PixelFormatEnum::ARGB8888;
+    let surface = Surface::new_with_format(w as usize, h as usize, 32, format)?;
+    let ret = unsafe {
+      let raw = surface.nn.as_ptr();
+      fermium::SDL_RenderReadPixels(
+        self.rend.nn.as_ptr(),
+        core::ptr::null(),
+        format.0,
+        (*raw).pixels,
+        (*raw).pitch,
+      )
+    };
+    if ret >= 0 {
+      Ok(surface)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Saves the current render target to a BMP file.
+  ///
+  /// The same timing rules as [`read_pixels`](Self::read_pixels) apply.
+  pub fn save_screenshot(&self, filename: &str) -> Result<(), SdlError> {
+    self.read_pixels()?.save_bmp(filename)
+  }
+
   pub fn create_texture(
     &self, pixel_format: PixelFormatEnum, access: TextureAccess, w: u32, h: u32,
   ) -> Result<Texture, SdlError> {
@@ -82,7 +337,11 @@ impl RendererWindow {
       )
     })
     .ok_or_else(sdl_get_error)
-    .map(|nn| Texture { nn, rend: self.rend.clone() })
+    .map(|nn| {
+      let bytes = texture_bytes(pixel_format.0, w, h);
+      self.rend.counters.add_texture_bytes(bytes);
+      Texture { nn, rend: self.rend.clone(), bytes }
+    })
   }
 
   pub fn create_texture_from_surface(
@@ -95,7 +354,39 @@ impl RendererWindow {
       )
     })
     .ok_or_else(sdl_get_error)
-    .map(|nn| Texture { nn, rend: self.rend.clone() })
+    .map(|nn| {
+      let mut texture = Texture { nn, rend: self.rend.clone(), bytes: 0 };
+      if let (Ok(format), Ok([w, h])) = (texture.pixel_format(), texture.size())
+      {
+        texture.bytes = texture_bytes(format.0, w, h);
+        self.rend.counters.add_texture_bytes(texture.bytes);
+      }
+      texture
+    })
+  }
+
+  /// Gets the `CAMetalLayer` that this renderer draws into.
+  ///
+  /// * Only available on Mac and iOS.
+  /// * Gives `None` if the renderer isn't using the Metal backend.
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  pub fn metal_layer(&self) -> Option<NonNull<c_void>> {
+    NonNull::new(unsafe {
+      fermium::SDL_RenderGetMetalLayer(self.rend.nn.as_ptr())
+    })
+  }
+
+  /// Gets the `MTLRenderCommandEncoder` for the current frame.
+  ///
+  /// * Only available on Mac and iOS.
+  /// * Gives `None` if the renderer isn't using the Metal backend.
+  /// * The encoder is only valid until the next call to
+  ///   [`present`](Self::present), so don't hold on to it.
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  pub fn metal_command_encoder(&self) -> Option<NonNull<c_void>> {
+    NonNull::new(unsafe {
+      fermium::SDL_RenderGetMetalCommandEncoder(self.rend.nn.as_ptr())
+    })
   }
 }
 
@@ -107,3 +398,11 @@ pub enum TextureAccess {
   /// Can be used as a render target.
   Target = fermium::SDL_TEXTUREACCESS_TARGET as _,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlipMode {
+  None = fermium::SDL_FLIP_NONE as _,
+  Horizontal = fermium::SDL_FLIP_HORIZONTAL as _,
+  Vertical = fermium::SDL_FLIP_VERTICAL as _,
+  Both = (fermium::SDL_FLIP_HORIZONTAL | fermium::SDL_FLIP_VERTICAL) as _,
+}
@@ -1,16 +1,28 @@
 use core::{
+  any::Any,
+  cell::{Cell, RefCell},
   convert::TryFrom,
   marker::PhantomData,
+  ops::RangeBounds,
   sync::atomic::{AtomicBool, Ordering},
+  time::Duration,
 };
 
-use alloc::{boxed::Box, string::String, sync::Arc};
+use alloc::{boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+
+use fermium::{SDL_Keymod, SDL_Rect, SDL_Scancode};
 
 use crate::{
-  sdl_get_error, AllowedAudioChanges, AudioCallbackDevice,
+  event_filter::replace_event_filter, event_kind_bounds, rw_from_file,
+  sdl_get_error, string_from_sdl, take_string_from_sdl,
+  user_event::free_live_payloads, AllowedAudioChanges, AudioCallbackDevice,
   AudioCallbackRequestSpec, AudioDeviceObtainedSpec, AudioQueueDevice,
-  AudioQueueRequestSpec, Controller, Event, RendererWindow, SdlError,
-  WindowCreationFlags,
+  AudioQueueRequestSpec, Controller, Cursor, Event, EventKind, EventWatch,
+  Finger, GestureID, Haptic, Joystick, JoystickDeviceIndex, JoystickGuid,
+  JoystickID, KeyModifiers, KeyboardState, Keycode, MouseButtons, MouseState,
+  PixelFormatEnum, Rect, RelativeMouseState, RendererWindow, Scancode,
+  SdlError, Surface, SystemCursor, Timestamp, TouchDeviceType, TouchID,
+  UserEventType, WindowCreationFlags,
 };
 
 static SDL_ACTIVE: AtomicBool = AtomicBool::new(false);
@@ -19,7 +31,11 @@ pub(crate) struct Initialization(PhantomData<*mut u8>);
 
 impl Drop for Initialization {
   fn drop(&mut self) {
+    // SDL forgets the filter when it quits, so free ours first.
+    replace_event_filter(None);
     unsafe { fermium::SDL_Quit() }
+    // SDL threw away the queue without freeing any user event payloads.
+    free_live_payloads();
     SDL_ACTIVE.store(false, Ordering::SeqCst)
   }
 }
@@ -70,6 +86,9 @@ impl InitFlags {
 pub struct Sdl {
   #[allow(dead_code)]
   init: Arc<Initialization>,
+  quit_requested: Cell<bool>,
+  /// The cursor that's set, kept here so that it isn't freed while in use.
+  cursor: RefCell<Option<Cursor>>,
 }
 impl core::fmt::Debug for Sdl {
   fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -85,7 +104,11 @@ impl Sdl {
   /// * On Mac, you must initialize SDL from the main thread.
   /// * You cannot double initialize SDL.
   pub fn init(flags: InitFlags) -> Result<Self, SdlError> {
-    Initialization::init(flags).map(|init| Self { init })
+    Initialization::init(flags).map(|init| Self {
+      init,
+      quit_requested: Cell::new(false),
+      cursor: RefCell::new(None),
+    })
   }
 
   /// Polls for a pending event.
@@ -93,38 +116,512 @@ impl Sdl {
   /// * Always returns immediately.
   /// * If no event is pending, gives `None`.
   /// * If the event from SDL can't be parsed, you also get `None`.
-  /// * The `u32` is the event's timestamp (milliseconds since SDL's
-  ///   initialization).
-  pub fn poll_event(&self) -> Option<(Event, u32)> {
+  /// * The [`Timestamp`] is when SDL got the event.
+  pub fn poll_event(&self) -> Option<(Event, Timestamp)> {
     use fermium::{SDL_Event, SDL_PollEvent};
     let mut sdl_event = SDL_Event::default();
     let ret = unsafe { SDL_PollEvent(&mut sdl_event) };
     if ret != 0 {
-      let timestamp = unsafe { sdl_event.common.timestamp };
-      Event::try_from(sdl_event).map(|ev| (ev, timestamp)).ok()
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      let event = Event::try_from(sdl_event).ok()?;
+      self.note_event(&event);
+      Some((event, timestamp))
     } else {
       None
     }
   }
 
+  /// Iterates over all pending events.
+  ///
+  /// * The iterator ends once the queue is empty, so it never blocks.
+  /// * Unlike `poll_event`, events that can't be parsed are skipped rather
+  ///   than ending the iteration early.
+  /// * The [`Timestamp`] is when SDL got the event.
+  pub fn poll_events(&self) -> PollEvents<'_> {
+    PollEvents { sdl: self }
+  }
+
+  /// Copies out the pending events of the given kinds, leaving them in the
+  /// queue.
+  ///
+  /// * This gets every matching event with one lock of the queue, rather than
+  ///   one call per event.
+  /// * This doesn't pump events from the OS into the queue, so it only sees
+  ///   what's already there (see [`pump_events`](Sdl::pump_events)).
+  /// * Events that can't be parsed are skipped.
+  /// * The [`Timestamp`] is when SDL got the event.
+  pub fn peek_events(
+    &self, range: impl RangeBounds<EventKind>,
+  ) -> Result<Vec<(Event, Timestamp)>, SdlError> {
+    let sdl_events = peep_events(range, fermium::SDL_PEEKEVENT)?;
+    // Safety: peeked events are still owned by the queue, and only this
+    // thread takes things out of the queue.
+    Ok(
+      sdl_events
+        .iter()
+        .filter_map(|sdl_event| unsafe {
+          let timestamp = Timestamp(sdl_event.common.timestamp);
+          Event::peek_from_sdl(sdl_event).ok().map(|ev| (ev, timestamp))
+        })
+        .collect(),
+    )
+  }
+
+  /// Takes all the pending events of the given kinds out of the queue.
+  ///
+  /// Events of other kinds are left in the queue. Otherwise this is the same
+  /// as [`peek_events`](Sdl::peek_events).
+  pub fn drain_events(
+    &self, range: impl RangeBounds<EventKind>,
+  ) -> Result<Vec<(Event, Timestamp)>, SdlError> {
+    let sdl_events = peep_events(range, fermium::SDL_GETEVENT)?;
+    let events: Vec<(Event, Timestamp)> = sdl_events
+      .into_iter()
+      .filter_map(|sdl_event| {
+        let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+        Event::try_from(sdl_event).ok().map(|ev| (ev, timestamp))
+      })
+      .collect();
+    events.iter().for_each(|(ev, _)| self.note_event(ev));
+    Ok(events)
+  }
+
+  /// Removes all the pending events of the given kinds from the queue.
+  ///
+  /// Unlike `SDL_FlushEvents`, this frees the data held by file drop and user
+  /// events. The events are thrown away unseen, so a flushed `Quit` doesn't
+  /// count for [`should_quit`](Sdl::should_quit).
+  pub fn flush_events(
+    &self, range: impl RangeBounds<EventKind>,
+  ) -> Result<(), SdlError> {
+    let sdl_events = peep_events(range, fermium::SDL_GETEVENT)?;
+    // Parsing is what frees the data, so it's done even though the events are
+    // dropped right away.
+    sdl_events.into_iter().for_each(|sdl_event| {
+      let _ = Event::try_from(sdl_event);
+    });
+    Ok(())
+  }
+
+  /// Sets if events of a given kind are put into the queue at all.
+  ///
+  /// Disabling a kind also flushes any of that kind that are already queued.
+  /// Disabled events never reach an event filter or watch either.
+  pub fn set_event_enabled(&self, kind: EventKind, enabled: bool) {
+    if !enabled {
+      // SDL would flush these itself, but it'd leak any data they hold.
+      let _ = self.flush_events(kind..=kind);
+    }
+    let state =
+      if enabled { fermium::SDL_ENABLE } else { fermium::SDL_DISABLE };
+    unsafe { fermium::SDL_EventState(kind.0, state as _) };
+  }
+
+  /// If events of a given kind are put into the queue.
+  pub fn is_event_enabled(&self, kind: EventKind) -> bool {
+    let state = unsafe { fermium::SDL_EventState(kind.0, fermium::SDL_QUERY) };
+    state == fermium::SDL_ENABLE as u8
+  }
+
+  /// The time since SDL was initialized, on the same clock as event
+  /// timestamps.
+  ///
+  /// Compare this against an event's timestamp to see how long ago it
+  /// happened.
+  pub fn ticks(&self) -> Timestamp {
+    Timestamp(unsafe { fermium::SDL_GetTicks() })
+  }
+
+  /// If a [`Event::Quit`] has come out of the event queue.
+  ///
+  /// Simple programs can just loop until this is true. This only tracks the
+  /// whole program being asked to quit: a [`WindowEvent::Close`] is just one
+  /// window asking to close (see [`Event::close_requested`]).
+  ///
+  /// The flag is set by all the ways of taking events out of the queue, but
+  /// not by peeking.
+  pub fn should_quit(&self) -> bool {
+    self.quit_requested.get()
+  }
+
+  /// Clears the flag that [`should_quit`](Sdl::should_quit) checks, eg: if
+  /// the user picked "cancel" in an "are you sure?" box.
+  pub fn cancel_quit(&self) {
+    self.quit_requested.set(false)
+  }
+
+  /// Updates anything we track about the events that come out of the queue.
+  pub(crate) fn note_event(&self, event: &Event) {
+    if let Event::Quit = event {
+      self.quit_requested.set(true);
+    }
+  }
+
+  /// Gathers events from the OS into the event queue.
+  ///
+  /// Polling and waiting do this for you. Call this yourself if you read input
+  /// through state queries (eg: the keyboard state) instead of the queue, or
+  /// before using [`peek_events`](Sdl::peek_events).
+  pub fn pump_events(&self) {
+    unsafe { fermium::SDL_PumpEvents() }
+  }
+
+  /// Gets which keys are currently held.
+  ///
+  /// SDL updates this as it gathers events, so poll (or pump) events first to
+  /// get the latest state.
+  pub fn keyboard_state(&self) -> KeyboardState {
+    let mut numkeys = 0;
+    let ptr = unsafe { fermium::SDL_GetKeyboardState(&mut numkeys) };
+    let keys = if ptr.is_null() {
+      Vec::new()
+    } else {
+      unsafe { core::slice::from_raw_parts(ptr, numkeys.max(0) as usize) }
+        .to_vec()
+    };
+    KeyboardState { keys }
+  }
+
+  /// Gets the modifier keys that are held, and the lock keys that are on.
+  ///
+  /// Like [`keyboard_state`](Sdl::keyboard_state), this is updated as SDL
+  /// gathers events.
+  pub fn get_mod_state(&self) -> KeyModifiers {
+    KeyModifiers(unsafe { fermium::SDL_GetModState() } as u16)
+  }
+
+  /// Overrides SDL's idea of which modifiers are active.
+  ///
+  /// This doesn't change the actual keyboard (the caps lock light won't turn
+  /// on), only what SDL reports until the keys are next pressed.
+  pub fn set_mod_state(&self, modifiers: KeyModifiers) {
+    unsafe { fermium::SDL_SetModState(modifiers.0 as SDL_Keymod) }
+  }
+
+  /// The key that's at a physical location in the current keyboard layout.
+  ///
+  /// The layout is only known once video is initialized, until then this
+  /// gives `Unknown`.
+  pub fn get_key_from_scancode(&self, scancode: Scancode) -> Keycode {
+    Keycode::from_raw(unsafe {
+      fermium::SDL_GetKeyFromScancode(scancode.as_raw() as SDL_Scancode)
+    })
+  }
+
+  /// Where a key is physically located in the current keyboard layout.
+  pub fn get_scancode_from_key(&self, keycode: Keycode) -> Scancode {
+    Scancode::from_raw(unsafe {
+      fermium::SDL_GetScancodeFromKey(keycode.as_raw())
+    } as u32)
+  }
+
+  /// A readable name for a key, such as `"Left Shift"` or `"Q"`.
+  ///
+  /// * Keys that make a character are named with that character in
+  ///   uppercase.
+  /// * Keys without a name give an empty string.
+  pub fn get_key_name(&self, keycode: Keycode) -> String {
+    // Note: SDL writes character key names into one static buffer,
+    // so we copy the name out right away. `Sdl` not being `Send` keeps other
+    // threads from using the buffer at the same time.
+    unsafe { string_from_sdl(fermium::SDL_GetKeyName(keycode.as_raw()).cast()) }
+  }
+
+  /// A readable name for a physical key location.
+  ///
+  /// This names the key on a US keyboard, so usually you want
+  /// [`get_key_name`](Sdl::get_key_name) to show keys to the user. Scancodes
+  /// without a name give an empty string.
+  pub fn get_scancode_name(&self, scancode: Scancode) -> String {
+    unsafe {
+      string_from_sdl(
+        fermium::SDL_GetScancodeName(scancode.as_raw() as SDL_Scancode).cast(),
+      )
+    }
+  }
+
+  /// The label that a user would see on the key at a physical location, in
+  /// their keyboard layout.
+  ///
+  /// Use this to show controls that are bound by scancode. For example, the
+  /// `W` scancode is labeled "Z" on a French keyboard.
+  ///
+  /// * If the layout doesn't give the key a name (or video isn't initialized
+  ///   yet), this falls back to the US keyboard name of the scancode.
+  /// * If that's empty too, you get the name of the `Scancode` variant.
+  pub fn scancode_label(&self, scancode: Scancode) -> String {
+    let keycode = self.get_key_from_scancode(scancode);
+    if keycode != Keycode::Unknown {
+      let name = self.get_key_name(keycode);
+      if !name.is_empty() {
+        return name;
+      }
+    }
+    let name = self.get_scancode_name(scancode);
+    if !name.is_empty() {
+      return name;
+    }
+    alloc::format!("{:?}", scancode)
+  }
+
+  /// Looks up a key by the name that [`get_key_name`](Sdl::get_key_name)
+  /// gives.
+  pub fn get_key_from_name(&self, name: &str) -> Option<Keycode> {
+    let name_null: Vec<u8> =
+      name.as_bytes().iter().copied().chain(Some(0)).collect();
+    match Keycode::from_raw(unsafe {
+      fermium::SDL_GetKeyFromName(name_null.as_ptr().cast())
+    }) {
+      Keycode::Unknown => None,
+      keycode => Some(keycode),
+    }
+  }
+
+  /// Looks up a scancode by the name that
+  /// [`get_scancode_name`](Sdl::get_scancode_name) gives.
+  pub fn get_scancode_from_name(&self, name: &str) -> Option<Scancode> {
+    let name_null: Vec<u8> =
+      name.as_bytes().iter().copied().chain(Some(0)).collect();
+    match Scancode::from_raw(unsafe {
+      fermium::SDL_GetScancodeFromName(name_null.as_ptr().cast())
+    } as u32)
+    {
+      Scancode::Unknown => None,
+      scancode => Some(scancode),
+    }
+  }
+
+  /// Starts sending [`Event::TextInput`] and [`Event::TextEditing`] events.
+  ///
+  /// * On desktops text input starts out active, but on phones and tablets
+  ///   this is what brings up the on screen keyboard.
+  /// * Call [`set_text_input_rect`](Sdl::set_text_input_rect) too, so that
+  ///   any IME window shows up in the right place.
+  pub fn start_text_input(&self) {
+    unsafe { fermium::SDL_StartTextInput() }
+  }
+
+  /// Stops sending text events, and hides the on screen keyboard.
+  pub fn stop_text_input(&self) {
+    unsafe { fermium::SDL_StopTextInput() }
+  }
+
+  /// If text input events are being sent.
+  pub fn is_text_input_active(&self) -> bool {
+    let active = unsafe { fermium::SDL_IsTextInputActive() };
+    active == fermium::SDL_TRUE
+  }
+
+  /// Sets the area where text is being typed, in window coordinates.
+  ///
+  /// IME candidate windows are placed next to this, and on mobile the window
+  /// is panned so that the on screen keyboard doesn't cover it.
+  pub fn set_text_input_rect(&self, rect: Rect) {
+    let mut sdl_rect = SDL_Rect::from(rect);
+    unsafe { fermium::SDL_SetTextInputRect(&mut sdl_rect) }
+  }
+
+  /// If the platform has an on screen keyboard.
+  ///
+  /// When it does, [`start_text_input`](Sdl::start_text_input) brings it up,
+  /// and you can check if it's showing with
+  /// [`Window::is_screen_keyboard_shown`](crate::Window::is_screen_keyboard_shown).
+  /// Otherwise you might want to draw your own.
+  pub fn has_screen_keyboard_support(&self) -> bool {
+    let support = unsafe { fermium::SDL_HasScreenKeyboardSupport() };
+    support == fermium::SDL_TRUE
+  }
+
+  /// Gets the mouse position within the focused window, and the held buttons.
+  ///
+  /// Like [`keyboard_state`](Sdl::keyboard_state), this is updated as SDL
+  /// gathers events.
+  pub fn mouse_state(&self) -> MouseState {
+    let (mut x_pos, mut y_pos) = (0, 0);
+    let buttons = unsafe { fermium::SDL_GetMouseState(&mut x_pos, &mut y_pos) };
+    MouseState { x_pos, y_pos, buttons: MouseButtons(buttons) }
+  }
+
+  /// Gets the mouse position on the desktop, and the held buttons.
+  ///
+  /// This asks the OS directly, so it's up to date even if you haven't
+  /// gathered events, and it works when the mouse is outside of any window.
+  /// The position is in the same coordinates as display bounds.
+  pub fn global_mouse_state(&self) -> MouseState {
+    let (mut x_pos, mut y_pos) = (0, 0);
+    let buttons =
+      unsafe { fermium::SDL_GetGlobalMouseState(&mut x_pos, &mut y_pos) };
+    MouseState { x_pos, y_pos, buttons: MouseButtons(buttons) }
+  }
+
+  /// Moves the mouse cursor to a position on the desktop.
+  ///
+  /// The position is in the same coordinates as display bounds. To move it
+  /// within a window use [`Window::warp_mouse`](crate::Window::warp_mouse).
+  ///
+  /// ## Failure
+  /// * If the platform can't move the cursor.
+  pub fn warp_mouse_global(&self, [x, y]: [i32; 2]) -> Result<(), SdlError> {
+    let ret = unsafe { fermium::SDL_WarpMouseGlobal(x, y) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Keeps sending mouse events to the focused window, even once the mouse
+  /// leaves it.
+  ///
+  /// Turn this on when a drag starts and off when it ends. While it's on,
+  /// positions outside the window are given relative to the window (so they
+  /// can be negative), and [`global_mouse_state`](Sdl::global_mouse_state)
+  /// is a good way to track the mouse.
+  ///
+  /// ## Failure
+  /// * If the platform can't capture the mouse.
+  pub fn capture_mouse(&self, enabled: bool) -> Result<(), SdlError> {
+    let ret = unsafe {
+      fermium::SDL_CaptureMouse(if enabled {
+        fermium::SDL_TRUE
+      } else {
+        fermium::SDL_FALSE
+      })
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Makes a cursor from a surface, such as one loaded from an image.
+  ///
+  /// `hotspot` is the pixel of the image that's the "tip" of the cursor (the
+  /// point that gets clicked).
+  ///
+  /// ## Failure
+  /// * If the platform doesn't have cursors.
+  /// * If the surface is too big for the platform's cursors.
+  pub fn create_color_cursor(
+    &self, surface: &Surface, hotspot: [i32; 2],
+  ) -> Result<Cursor, SdlError> {
+    Cursor::from_surface(self.init.clone(), surface, hotspot)
+  }
+
+  /// Makes one of the system's standard cursors.
+  ///
+  /// ## Failure
+  /// * If the platform doesn't have cursors.
+  pub fn create_system_cursor(
+    &self, system_cursor: SystemCursor,
+  ) -> Result<Cursor, SdlError> {
+    Cursor::system(self.init.clone(), system_cursor)
+  }
+
+  /// Sets the mouse cursor.
+  ///
+  /// The cursor is held until a different one is set, so it's fine to drop
+  /// your copy.
+  pub fn set_cursor(&self, cursor: &Cursor) {
+    unsafe { fermium::SDL_SetCursor(cursor.as_ptr()) }
+    // Note: Only free the old cursor once it's no longer set.
+    self.cursor.replace(Some(cursor.clone()));
+  }
+
+  /// Sets the mouse cursor back to the system's normal cursor.
+  pub fn reset_cursor(&self) {
+    unsafe { fermium::SDL_SetCursor(fermium::SDL_GetDefaultCursor()) }
+    self.cursor.replace(None);
+  }
+
+  /// Shows or hides the mouse cursor.
+  ///
+  /// Hide it if you draw your own cursor. The cursor is only hidden while
+  /// it's over one of your windows.
+  ///
+  /// ## Failure
+  /// * If the platform doesn't have cursors.
+  pub fn set_cursor_visible(&self, visible: bool) -> Result<(), SdlError> {
+    let toggle = if visible {
+      fermium::SDL_ENABLE as i32
+    } else {
+      fermium::SDL_DISABLE as i32
+    };
+    let ret = unsafe { fermium::SDL_ShowCursor(toggle) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// If the mouse cursor is shown.
+  pub fn cursor_visible(&self) -> bool {
+    let ret = unsafe { fermium::SDL_ShowCursor(fermium::SDL_QUERY) };
+    ret == fermium::SDL_ENABLE as i32
+  }
+
+  /// Turns relative mouse mode on or off.
+  ///
+  /// In relative mode the cursor is hidden and held in place, and the mouse
+  /// keeps reporting motion (the `dx` and `dy` of motion events) no matter
+  /// how far it moves. This is what first person camera controls want.
+  ///
+  /// ## Failure
+  /// * If the platform can't do relative mode.
+  pub fn set_relative_mouse_mode(&self, enabled: bool) -> Result<(), SdlError> {
+    let ret = unsafe {
+      fermium::SDL_SetRelativeMouseMode(if enabled {
+        fermium::SDL_TRUE
+      } else {
+        fermium::SDL_FALSE
+      })
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// If relative mouse mode is on.
+  pub fn relative_mouse_mode(&self) -> bool {
+    let enabled = unsafe { fermium::SDL_GetRelativeMouseMode() };
+    enabled == fermium::SDL_TRUE
+  }
+
+  /// Gets how far the mouse has moved since the last call, and the held
+  /// buttons.
+  ///
+  /// Like [`mouse_state`](Sdl::mouse_state), this is updated as SDL gathers
+  /// events. Call it once per frame for per frame motion.
+  pub fn relative_mouse_state(&self) -> RelativeMouseState {
+    let (mut dx, mut dy) = (0, 0);
+    let buttons =
+      unsafe { fermium::SDL_GetRelativeMouseState(&mut dx, &mut dy) };
+    RelativeMouseState { dx, dy, buttons: MouseButtons(buttons) }
+  }
+
   /// Waits for a pending event.
   ///
   /// * Blocks if no event is available.
   /// * Returns `Err` if there's a problem during the wait.
-  /// * The `u32` is the event's timestamp (milliseconds since SDL's
-  ///   initialization).
-  pub fn wait_event(&self) -> Result<(Event, u32), SdlError> {
+  /// * The [`Timestamp`] is when SDL got the event.
+  pub fn wait_event(&self) -> Result<(Event, Timestamp), SdlError> {
     use fermium::{SDL_Event, SDL_WaitEvent};
     let mut sdl_event = SDL_Event::default();
     let ret = unsafe { SDL_WaitEvent(&mut sdl_event) };
     if ret != 0 {
-      let timestamp = unsafe { sdl_event.common.timestamp };
-      Event::try_from(sdl_event).map(|ev| (ev, timestamp)).map_err(|()| {
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      let event = Event::try_from(sdl_event).map_err(|()| {
         SdlError(Box::new(alloc::format!(
           "Could not parse event, {:?}",
           sdl_event
         )))
-      })
+      })?;
+      self.note_event(&event);
+      Ok((event, timestamp))
     } else {
       Err(sdl_get_error())
     }
@@ -132,30 +629,96 @@ impl Sdl {
 
   /// Waits for a pending event, but with a timeout.
   ///
-  /// * Blocks if no event is available, up to the given number of milliseconds.
+  /// * Blocks if no event is available, up to the given timeout. SDL counts
+  ///   the timeout in whole milliseconds, so it's rounded down to that.
   /// * Returns `Err` if there's a problem during the wait, or if the wait timed
   ///   out.
-  /// * The `u32` is the event's timestamp (milliseconds since SDL's
-  ///   initialization).
+  /// * The [`Timestamp`] is when SDL got the event.
   pub fn wait_event_timeout(
-    &self, milliseconds: i32,
-  ) -> Result<(Event, u32), SdlError> {
+    &self, timeout: Duration,
+  ) -> Result<(Event, Timestamp), SdlError> {
     use fermium::{SDL_Event, SDL_WaitEventTimeout};
+    let milliseconds = timeout.as_millis().min(i32::MAX as u128) as i32;
     let mut sdl_event = SDL_Event::default();
     let ret = unsafe { SDL_WaitEventTimeout(&mut sdl_event, milliseconds) };
     if ret != 0 {
-      let timestamp = unsafe { sdl_event.common.timestamp };
-      Event::try_from(sdl_event).map(|ev| (ev, timestamp)).map_err(|()| {
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      let event = Event::try_from(sdl_event).map_err(|()| {
         SdlError(Box::new(alloc::format!(
           "Could not parse event, {:?}",
           sdl_event
         )))
-      })
+      })?;
+      self.note_event(&event);
+      Ok((event, timestamp))
     } else {
       Err(sdl_get_error())
     }
   }
 
+  /// Sets a closure that decides which events get into the event queue.
+  ///
+  /// The closure sees each event as it's pushed, and returns `false` to drop
+  /// it. On iOS and Android some events (eg: the app going into the
+  /// background) have to be handled right when they happen, and a filter is
+  /// the way to do that.
+  ///
+  /// * The closure runs on whichever thread pushed the event, so it must be
+  ///   `Send`. SDL never runs it on two threads at once.
+  /// * Events that beryllium can't parse are always kept.
+  /// * Setting a filter replaces (and drops) any previous one.
+  /// * Any events already in the queue are discarded.
+  /// * If the closure panics the event is kept (with `std`), or the program
+  ///   aborts (without `std`).
+  pub fn set_event_filter<F>(&self, filter: F)
+  where
+    F: FnMut(&Event) -> bool + Send + 'static,
+  {
+    replace_event_filter(Some(Box::new(Box::new(filter))))
+  }
+
+  /// Removes the event filter, if one is set.
+  ///
+  /// Any events already in the queue are discarded.
+  pub fn clear_event_filter(&self) {
+    replace_event_filter(None)
+  }
+
+  /// Adds a closure that sees every event as it's pushed.
+  ///
+  /// Unlike a filter, a watch can't drop events, and there can be any number
+  /// of them. On Windows and Mac the main thread is stuck inside SDL while
+  /// the user drags to resize a window, so `poll_event` doesn't return until
+  /// the drag ends. A watch still runs during the drag, so it's where you'd
+  /// redraw to get live resizing.
+  ///
+  /// * The closure runs on whichever thread pushed the event, so it must be
+  ///   `Send`. SDL never runs it on two threads at once.
+  /// * Events that beryllium can't parse are skipped.
+  /// * If the closure panics the panic is caught (with `std`), or the program
+  ///   aborts (without `std`).
+  /// * Dropping the returned [`EventWatch`] removes the watch.
+  pub fn add_event_watch<F>(&self, watch: F) -> EventWatch
+  where
+    F: FnMut(&Event) + Send + 'static,
+  {
+    EventWatch::add(self.init.clone(), watch)
+  }
+
+  /// Registers a new type of user event, which carries values of type `T`.
+  ///
+  /// Values pushed with the returned handle come back out of the event queue
+  /// as [`Event::User`]. Each call registers a new event type, even for the
+  /// same `T`.
+  ///
+  /// ## Failure
+  /// * SDL only has room for about 32,000 user event types.
+  pub fn register_user_event<T: Any + Send + Sync>(
+    &self,
+  ) -> Result<UserEventType<T>, SdlError> {
+    UserEventType::register()
+  }
+
   /// Creates a new window that uses SDL2's 2D rendering system.
   pub fn new_renderer_window(
     &self, title: &str, pos: Option<[i32; 2]>, size: [u32; 2],
@@ -164,6 +727,24 @@ impl Sdl {
     RendererWindow::new(self.init.clone(), title, pos, size, flags)
   }
 
+  /// Loads a BMP file into a new surface.
+  ///
+  /// This is the same as [`Surface::load_from_bmp`].
+  pub fn load_bmp(&self, filename: &str) -> Result<Surface, SdlError> {
+    Surface::load_from_bmp(filename)
+  }
+
+  /// Makes a new, zeroed surface that uses the given pixel format.
+  ///
+  /// The bit depth is taken from the format, so there's no need to know the
+  /// masks for common formats such as `RGBA32`.
+  pub fn create_surface_with_format(
+    &self, width: usize, height: usize, format: PixelFormatEnum,
+  ) -> Result<Surface, SdlError> {
+    let bit_depth = fermium::SDL_BITSPERPIXEL(format.0 as _) as usize;
+    Surface::new_with_format(width, height, bit_depth, format)
+  }
+
   pub fn get_number_of_joysticks(&self) -> Result<usize, SdlError> {
     let ret = unsafe { fermium::SDL_NumJoysticks() };
     if ret >= 0 {
@@ -173,8 +754,357 @@ impl Sdl {
     }
   }
 
-  pub fn open_controller(&self, id: usize) -> Result<Controller, SdlError> {
-    Controller::open(self.init.clone(), id)
+  /// The GUID of a joystick by device index, without opening it.
+  ///
+  /// ## Failure
+  /// * If the index is out of range.
+  pub fn get_joystick_device_guid(
+    &self, index: JoystickDeviceIndex,
+  ) -> Result<JoystickGuid, SdlError> {
+    let index = index.to_sdl()?;
+    let guid =
+      JoystickGuid(unsafe { fermium::SDL_JoystickGetDeviceGUID(index) }.data);
+    if guid != JoystickGuid::default() {
+      Ok(guid)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// The instance ID that a joystick by device index has (or will have, once
+  /// it's opened).
+  pub fn get_joystick_device_instance_id(
+    &self, index: JoystickDeviceIndex,
+  ) -> Option<JoystickID> {
+    let index = index.to_sdl().ok()?;
+    let id = unsafe { fermium::SDL_JoystickGetDeviceInstanceID(index) };
+    if id >= 0 {
+      Some(JoystickID(id))
+    } else {
+      None
+    }
+  }
+
+  /// The number of haptic (force feedback) devices.
+  pub fn get_number_of_haptics(&self) -> Result<usize, SdlError> {
+    let ret = unsafe { fermium::SDL_NumHaptics() };
+    if ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// The name of a haptic device, by index.
+  pub fn get_haptic_name(&self, index: usize) -> Option<String> {
+    let p = unsafe { fermium::SDL_HapticName(index as i32) };
+    if p.is_null() {
+      None
+    } else {
+      Some(unsafe { string_from_sdl(p.cast()) })
+    }
+  }
+
+  /// Opens a haptic device by index.
+  ///
+  /// Haptic indexes are separate from joystick indexes. To get the force
+  /// feedback of a joystick use [`Joystick::open_haptic`] instead.
+  pub fn open_haptic(&self, index: usize) -> Result<Haptic<'static>, SdlError> {
+    Haptic::open(self.init.clone(), index)
+  }
+
+  /// The number of touch devices (touch screens and trackpads).
+  ///
+  /// Devices only show up here once they've been touched on some platforms.
+  pub fn get_number_of_touch_devices(&self) -> usize {
+    unsafe { fermium::SDL_GetNumTouchDevices() }.max(0) as usize
+  }
+
+  /// The ID of a touch device, by index.
+  pub fn get_touch_device(&self, index: usize) -> Option<TouchID> {
+    let touch_id = unsafe { fermium::SDL_GetTouchDevice(index as i32) };
+    if touch_id != 0 {
+      Some(TouchID(touch_id))
+    } else {
+      None
+    }
+  }
+
+  /// What sort of device a touch device is.
+  pub fn get_touch_device_type(
+    &self, touch_id: TouchID,
+  ) -> Option<TouchDeviceType> {
+    TouchDeviceType::try_from(unsafe {
+      fermium::SDL_GetTouchDeviceType(touch_id.0)
+    })
+    .ok()
+  }
+
+  /// The number of fingers that are down on a touch device.
+  ///
+  /// Like [`mouse_state`](Sdl::mouse_state), this is updated as SDL gathers
+  /// events.
+  pub fn get_number_of_touch_fingers(&self, touch_id: TouchID) -> usize {
+    unsafe { fermium::SDL_GetNumTouchFingers(touch_id.0) }.max(0) as usize
+  }
+
+  /// A finger that's down on a touch device, by index.
+  ///
+  /// Indexes aren't kept for a finger between frames, so match fingers up
+  /// with their `finger_id`.
+  pub fn get_touch_finger(
+    &self, touch_id: TouchID, index: usize,
+  ) -> Option<Finger> {
+    let finger =
+      unsafe { fermium::SDL_GetTouchFinger(touch_id.0, index as i32) };
+    if finger.is_null() {
+      None
+    } else {
+      Some(Finger::from(unsafe { *finger }))
+    }
+  }
+
+  /// Starts recording a new "$1" gesture template.
+  ///
+  /// * `None` records on all touch devices.
+  /// * The next gesture made is recorded, and you get an
+  ///   [`Event::DollarRecord`] with the new template's ID. After that, gestures
+  ///   that match it give [`Event::DollarGesture`] events.
+  ///
+  /// ## Failure
+  /// * If the touch device isn't known (or there aren't any).
+  pub fn record_gesture(
+    &self, touch_id: Option<TouchID>,
+  ) -> Result<(), SdlError> {
+    let ret =
+      unsafe { fermium::SDL_RecordGesture(touch_id.map_or(-1, |t| t.0)) };
+    if ret > 0 {
+      Ok(())
+    } else {
+      Err(SdlError(Box::new(String::from(
+        "beryllium: no touch device to record a gesture on.",
+      ))))
+    }
+  }
+
+  /// Saves one gesture template to a file.
+  ///
+  /// ## Failure
+  /// * If there's no template with that ID, or the file can't be written.
+  pub fn save_dollar_template(
+    &self, gesture_id: GestureID, filename: &str,
+  ) -> Result<(), SdlError> {
+    let rw_ops = rw_from_file(filename, b"wb\0")?;
+    let ret = unsafe { fermium::SDL_SaveDollarTemplate(gesture_id.0, rw_ops) };
+    let close_ret = unsafe { fermium::SDL_RWclose(rw_ops) };
+    if ret > 0 && close_ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Saves all of the gesture templates to a file, giving how many there
+  /// were.
+  ///
+  /// ## Failure
+  /// * If the file can't be written.
+  pub fn save_all_dollar_templates(
+    &self, filename: &str,
+  ) -> Result<usize, SdlError> {
+    let rw_ops = rw_from_file(filename, b"wb\0")?;
+    let ret = unsafe { fermium::SDL_SaveAllDollarTemplates(rw_ops) };
+    let close_ret = unsafe { fermium::SDL_RWclose(rw_ops) };
+    if ret >= 0 && close_ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Loads gesture templates from a file, giving how many were loaded.
+  ///
+  /// * `None` loads them for all touch devices.
+  /// * The templates keep the IDs that they were saved with.
+  ///
+  /// ## Failure
+  /// * If the file can't be opened.
+  /// * If the touch device isn't known.
+  pub fn load_dollar_templates(
+    &self, touch_id: Option<TouchID>, filename: &str,
+  ) -> Result<usize, SdlError> {
+    let rw_ops = rw_from_file(filename, b"rb\0")?;
+    let ret = unsafe {
+      fermium::SDL_LoadDollarTemplates(touch_id.map_or(-1, |t| t.0), rw_ops)
+    };
+    unsafe { fermium::SDL_RWclose(rw_ops) };
+    if ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Loads gesture templates that are already in memory (eg: from
+  /// `include_bytes!`), giving how many were loaded.
+  ///
+  /// This works like [`load_dollar_templates`](Sdl::load_dollar_templates).
+  pub fn load_dollar_templates_from_bytes(
+    &self, touch_id: Option<TouchID>, bytes: &[u8],
+  ) -> Result<usize, SdlError> {
+    let rw_ops = unsafe {
+      fermium::SDL_RWFromConstMem(bytes.as_ptr().cast(), bytes.len() as i32)
+    };
+    if rw_ops.is_null() {
+      return Err(sdl_get_error());
+    }
+    let ret = unsafe {
+      fermium::SDL_LoadDollarTemplates(touch_id.map_or(-1, |t| t.0), rw_ops)
+    };
+    unsafe { fermium::SDL_RWclose(rw_ops) };
+    if ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Adds a controller mapping, or replaces the mapping for that GUID.
+  ///
+  /// Mappings look like `"GUID,name,a:b0,b:b1,..."`, the same as the lines
+  /// of the community `gamecontrollerdb.txt`. Gives `true` if the mapping is
+  /// new, and `false` if it replaced one.
+  ///
+  /// ## Failure
+  /// * If the mapping string isn't valid.
+  pub fn add_controller_mapping(
+    &self, mapping: &str,
+  ) -> Result<bool, SdlError> {
+    let mapping_null: Vec<u8> =
+      mapping.as_bytes().iter().copied().chain(Some(0)).collect();
+    let ret = unsafe {
+      fermium::SDL_GameControllerAddMapping(mapping_null.as_ptr().cast())
+    };
+    if ret >= 0 {
+      Ok(ret == 1)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// The mapping string for a joystick GUID, if there is one.
+  pub fn controller_mapping_for_guid(
+    &self, guid: JoystickGuid,
+  ) -> Option<String> {
+    let sdl_guid = fermium::SDL_JoystickGUID { data: guid.0 };
+    unsafe {
+      take_string_from_sdl(
+        fermium::SDL_GameControllerMappingForGUID(sdl_guid).cast(),
+      )
+    }
+  }
+
+  /// Adds all the controller mappings in a file, giving how many there were.
+  ///
+  /// This is for the community `gamecontrollerdb.txt`, or any other file of
+  /// mapping lines. Mappings for other platforms are skipped.
+  ///
+  /// ## Failure
+  /// * If the file can't be read.
+  pub fn add_controller_mappings_from_file(
+    &self, filename: &str,
+  ) -> Result<usize, SdlError> {
+    let rw_ops = rw_from_file(filename, b"rb\0")?;
+    // Note: SDL closes the file for us.
+    let ret =
+      unsafe { fermium::SDL_GameControllerAddMappingsFromRW(rw_ops, 1) };
+    if ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Adds all the controller mappings that are already in memory (eg: from
+  /// `include_str!`), giving how many there were.
+  ///
+  /// This works like
+  /// [`add_controller_mappings_from_file`](Sdl::add_controller_mappings_from_file).
+  pub fn add_controller_mappings_from_bytes(
+    &self, bytes: &[u8],
+  ) -> Result<usize, SdlError> {
+    let rw_ops = unsafe {
+      fermium::SDL_RWFromConstMem(bytes.as_ptr().cast(), bytes.len() as i32)
+    };
+    if rw_ops.is_null() {
+      return Err(sdl_get_error());
+    }
+    let ret =
+      unsafe { fermium::SDL_GameControllerAddMappingsFromRW(rw_ops, 1) };
+    if ret >= 0 {
+      Ok(ret as usize)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  pub fn open_controller(
+    &self, index: JoystickDeviceIndex,
+  ) -> Result<Controller, SdlError> {
+    Controller::open(self.init.clone(), index)
+  }
+
+  /// Opens a joystick by device index, see [`Joystick`].
+  ///
+  /// Controllers can also be opened this way, to read them as plain inputs.
+  pub fn open_joystick(
+    &self, index: JoystickDeviceIndex,
+  ) -> Result<Joystick, SdlError> {
+    Joystick::open(self.init.clone(), index)
+  }
+
+  // TODO: Virtual joysticks (`SDL_JoystickAttachVirtual`,
+  // `SDL_JoystickDetachVirtual`, and `SDL_JoystickSetVirtualAxis`/`Button`/
+  // `Hat`), once we bind SDL 2.0.14. `SDL_JoystickAttachVirtualEx` is from
+  // 2.0.24.
+
+  /// Gets the open controller with the given instance ID, such as from a
+  /// controller event.
+  ///
+  /// This gives a new handle to the same controller, and it stays open until
+  /// all of the handles are dropped. If no controller with that ID is open,
+  /// you get `None`.
+  pub fn controller_from_instance_id(
+    &self, id: JoystickID,
+  ) -> Option<Controller> {
+    Controller::from_instance_id(self.init.clone(), id)
+  }
+
+  /// Sets if controller events are sent at all.
+  ///
+  /// If you only ever check controllers directly you can turn these off, so
+  /// that the same input isn't handled twice. Then call
+  /// [`update_controllers`](Sdl::update_controllers) each frame instead, so
+  /// that the controller state still changes.
+  pub fn set_controller_events_enabled(&self, enabled: bool) {
+    let state =
+      if enabled { fermium::SDL_ENABLE } else { fermium::SDL_DISABLE };
+    unsafe { fermium::SDL_GameControllerEventState(state as _) };
+  }
+
+  /// If controller events are sent.
+  pub fn controller_events_enabled(&self) -> bool {
+    let state =
+      unsafe { fermium::SDL_GameControllerEventState(fermium::SDL_QUERY) };
+    state == fermium::SDL_ENABLE as i32
+  }
+
+  /// Updates the state of all open controllers.
+  ///
+  /// This happens on its own as events are polled, unless controller events
+  /// are turned off.
+  pub fn update_controllers(&self) {
+    unsafe { fermium::SDL_GameControllerUpdate() }
   }
 
   pub fn open_audio_queue_device(
@@ -203,3 +1133,55 @@ impl Sdl {
     )
   }
 }
+
+/// Peeks or gets all the queued events in the range.
+fn peep_events(
+  range: impl RangeBounds<EventKind>, action: fermium::SDL_eventaction,
+) -> Result<Vec<fermium::SDL_Event>, SdlError> {
+  use fermium::{SDL_Event, SDL_PeepEvents};
+  let (min, max) = event_kind_bounds(range);
+  if min > max {
+    return Ok(Vec::new());
+  }
+  // A null buffer gives the number of matching events.
+  let count = unsafe {
+    SDL_PeepEvents(core::ptr::null_mut(), 0, fermium::SDL_PEEKEVENT, min, max)
+  };
+  if count < 0 {
+    return Err(sdl_get_error());
+  }
+  let mut sdl_events = vec![SDL_Event::default(); count as usize];
+  let ret =
+    unsafe { SDL_PeepEvents(sdl_events.as_mut_ptr(), count, action, min, max) };
+  if ret < 0 {
+    return Err(sdl_get_error());
+  }
+  sdl_events.truncate(ret as usize);
+  Ok(sdl_events)
+}
+
+/// An iterator that drains the event queue.
+///
+/// See [`Sdl::poll_events`].
+#[derive(Debug)]
+pub struct PollEvents<'s> {
+  sdl: &'s Sdl,
+}
+impl Iterator for PollEvents<'_> {
+  type Item = (Event, Timestamp);
+  fn next(&mut self) -> Option<Self::Item> {
+    use fermium::{SDL_Event, SDL_PollEvent};
+    loop {
+      let mut sdl_event = SDL_Event::default();
+      let ret = unsafe { SDL_PollEvent(&mut sdl_event) };
+      if ret == 0 {
+        return None;
+      }
+      let timestamp = Timestamp(unsafe { sdl_event.common.timestamp });
+      if let Ok(ev) = Event::try_from(sdl_event) {
+        self.sdl.note_event(&ev);
+        return Some((ev, timestamp));
+      }
+    }
+  }
+}
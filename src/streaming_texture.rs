@@ -0,0 +1,85 @@
+use alloc::vec::Vec;
+
+use crate::{
+  PixelFormatEnum, Rect, RendererWindow, SdlError, Texture, TextureAccess,
+  TextureLock,
+};
+
+/// A ring of streaming textures, for uploading a new image every frame.
+///
+/// Locking a streaming texture that the GPU is still drawing from makes the
+/// driver wait for that draw to finish. This type avoids the stall by keeping
+/// two (or more) textures: you write into the back buffer while the front
+/// buffer is being drawn, then [`flip`](Self::flip) to swap their roles.
+pub struct StreamingTexture {
+  textures: Vec<Texture>,
+  front: usize,
+  pixel_format: PixelFormatEnum,
+  size: [u32; 2],
+}
+impl StreamingTexture {
+  /// Makes `count` streaming textures of the given format and size.
+  ///
+  /// ## Panics
+  /// * If `count` is less than 2.
+  pub fn new(
+    rend_win: &RendererWindow, pixel_format: PixelFormatEnum, w: u32, h: u32,
+    count: usize,
+  ) -> Result<Self, SdlError> {
+    assert!(count >= 2, "a StreamingTexture needs at least two textures");
+    let mut textures = Vec::with_capacity(count);
+    for _ in 0..count {
+      textures.push(rend_win.create_texture(
+        pixel_format,
+        TextureAccess::Streaming,
+        w,
+        h,
+      )?);
+    }
+    Ok(Self { textures, front: 0, pixel_format, size: [w, h] })
+  }
+
+  pub fn pixel_format(&self) -> PixelFormatEnum {
+    self.pixel_format
+  }
+
+  /// Width and height of each texture, in pixels.
+  pub fn size(&self) -> [u32; 2] {
+    self.size
+  }
+
+  /// The texture to draw this frame.
+  pub fn front(&self) -> &Texture {
+    &self.textures[self.front]
+  }
+
+  /// Locks the next texture in the ring so that you can write the next frame.
+  pub fn back_buffer(&mut self) -> Result<TextureLock<'_>, SdlError> {
+    self.back_buffer_rect(None)
+  }
+
+  /// As [`back_buffer`](Self::back_buffer), but only locks part of the
+  /// texture.
+  ///
+  /// ## Failure
+  /// * Planar YUV textures can only be locked whole, see
+  ///   [`Texture::lock`].
+  pub fn back_buffer_rect(
+    &mut self, rect: Option<Rect>,
+  ) -> Result<TextureLock<'_>, SdlError> {
+    let back = self.back_index();
+    self.textures[back].lock(rect)
+  }
+
+  /// Makes the back buffer into the front buffer.
+  ///
+  /// Call this once per frame, after writing the back buffer and before
+  /// drawing [`front`](Self::front).
+  pub fn flip(&mut self) {
+    self.front = self.back_index();
+  }
+
+  fn back_index(&self) -> usize {
+    (self.front + 1) % self.textures.len()
+  }
+}
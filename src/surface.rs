@@ -1,19 +1,19 @@
 use core::{
-  convert::TryInto,
+  convert::{TryFrom, TryInto},
   ops::{Deref, Index, IndexMut},
   ptr::NonNull,
 };
 
-use tinyvec::TinyVec;
+use alloc::{boxed::Box, string::String, vec::Vec};
 
-use fermium::{SDL_PixelFormat, SDL_Surface};
+use tinyvec::TinyVec;
 
-use crate::{sdl_get_error, PixelFormat, PixelFormatEnum, SdlError};
+use fermium::{SDL_PixelFormat, SDL_Rect, SDL_Surface};
 
-/*
-Some day maybe support SDL_CreateRGBSurfaceFrom and SDL_CreateRGBSurfaceWithFormatFrom,
-but that would need to be a whole separate type with a lifetime and PhantomData and all that.
-*/
+use crate::{
+  rect::opt_ptr, sdl_get_error, BlendMode, FlipMode, Palette, PixelFormat,
+  PixelFormatEnum, Rect, SdlError,
+};
 
 pub struct Surface {
   pub(crate) nn: NonNull<SDL_Surface>,
@@ -79,26 +79,666 @@ impl Surface {
     }
   }
 
-  // TODO: this is actually not needed for most surfaces, only ones that have
-  // RLE acceleration applied. It's not expensive for other surfaces to do the
-  // lock/unlock, but it's not very ergonomic.
-  pub fn lock(&mut self) -> Result<SurfaceLock<'_>, SdlError> {
-    let ret = unsafe { fermium::SDL_LockSurface(self.nn.as_ptr()) };
+  /// Loads a BMP image that's already in memory (eg: from `include_bytes!`).
+  pub fn from_bmp_bytes(bytes: &[u8]) -> Result<Self, SdlError> {
+    let rw_ops = unsafe {
+      fermium::SDL_RWFromConstMem(
+        bytes.as_ptr().cast(),
+        bytes.len().try_into().unwrap(),
+      )
+    };
+    if rw_ops.is_null() {
+      return Err(sdl_get_error());
+    }
+    NonNull::new(unsafe { fermium::SDL_LoadBMP_RW(rw_ops, true as _) })
+      .ok_or_else(sdl_get_error)
+      .map(|nn| Surface { nn })
+  }
+
+  /// Writes the surface to a BMP file.
+  pub fn save_bmp(&self, filename: &str) -> Result<(), SdlError> {
+    let filename_null: TinyVec<[u8; 64]> =
+      filename.as_bytes().iter().copied().chain(Some(0)).collect();
+    let rw_ops = unsafe {
+      fermium::SDL_RWFromFile(
+        filename_null.as_ptr().cast(),
+        b"wb\0".as_ptr().cast(),
+      )
+    };
+    if rw_ops.is_null() {
+      return Err(sdl_get_error());
+    }
+    let ret =
+      unsafe { fermium::SDL_SaveBMP_RW(self.nn.as_ptr(), rw_ops, true as _) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Copies all or part of `src` onto this surface.
+  ///
+  /// * `src_rect` of `None` copies all of `src`.
+  /// * Only the position of `dst_rect` is used, the size is taken from the
+  ///   source rect. `None` copies to the upper left corner.
+  /// * The result is clipped to this surface's clip rect, and the area that was
+  ///   actually drawn to is returned.
+  pub fn blit(
+    &mut self, src: &Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+  ) -> Result<Rect, SdlError> {
+    let src_rect: Option<SDL_Rect> = src_rect.map(Into::into);
+    let mut dst: SDL_Rect = dst_rect.unwrap_or_default().into();
+    let ret = unsafe {
+      fermium::SDL_UpperBlit(
+        src.nn.as_ptr(),
+        opt_ptr(&src_rect),
+        self.nn.as_ptr(),
+        &mut dst,
+      )
+    };
+    if ret >= 0 {
+      Ok(dst.into())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Copies all or part of `src` onto this surface, scaling it to fit.
+  ///
+  /// * `src_rect` of `None` copies all of `src`.
+  /// * `dst_rect` of `None` scales to cover this entire surface.
+  /// * The result is clipped to this surface's clip rect, and the area that was
+  ///   actually drawn to is returned.
+  pub fn blit_scaled(
+    &mut self, src: &Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+  ) -> Result<Rect, SdlError> {
+    let src_rect: Option<SDL_Rect> = src_rect.map(Into::into);
+    let mut dst: SDL_Rect = dst_rect
+      .unwrap_or_else(|| {
+        Rect::new(0, 0, self.width() as u32, self.height() as u32)
+      })
+      .into();
+    let ret = unsafe {
+      fermium::SDL_UpperBlitScaled(
+        src.nn.as_ptr(),
+        opt_ptr(&src_rect),
+        self.nn.as_ptr(),
+        &mut dst,
+      )
+    };
+    if ret >= 0 {
+      Ok(dst.into())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Copies all or part of this surface onto `dst`, stretching it to fit.
+  ///
+  /// Unlike [`blit_scaled`](Self::blit_scaled) this does no clipping or format
+  /// conversion, so it's a fairly direct way to resize images.
+  ///
+  /// * `None` for either rect uses the whole surface.
+  /// * `linear` filters with bilinear sampling instead of picking the nearest
+  ///   pixel. SDL 2.0.12 doesn't have a linear stretch of its own, so this is
+  ///   done in software, and only for formats with 4 bytes per pixel.
+  ///
+  /// ## Failure
+  /// * Both surfaces must be the same format.
+  /// * Both rects must be entirely within their surface.
+  pub fn stretch_into(
+    &self, dst: &mut Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+    linear: bool,
+  ) -> Result<(), SdlError> {
+    if linear {
+      return self.stretch_linear(dst, src_rect, dst_rect);
+    }
+    let src_rect: Option<SDL_Rect> = src_rect.map(Into::into);
+    let dst_rect: Option<SDL_Rect> = dst_rect.map(Into::into);
+    let ret = unsafe {
+      fermium::SDL_SoftStretch(
+        self.nn.as_ptr(),
+        opt_ptr(&src_rect),
+        dst.nn.as_ptr(),
+        opt_ptr(&dst_rect),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  fn stretch_linear(
+    &self, dst: &mut Surface, src_rect: Option<Rect>, dst_rect: Option<Rect>,
+  ) -> Result<(), SdlError> {
+    let err = |msg: &str| Err(SdlError(Box::new(String::from(msg))));
+    if self.pixel_format_enum() != dst.pixel_format_enum() {
+      return err(
+        "beryllium: can only stretch between surfaces of one format.",
+      );
+    }
+    if self.pixel_format().bytes_per_pixel() != 4 {
+      return err("beryllium: linear stretching needs 4 bytes per pixel.");
+    }
+    let s = match rect_within(src_rect, self) {
+      Some(r) => r,
+      None => return err("beryllium: invalid source stretch rect."),
+    };
+    let d = match rect_within(dst_rect, dst) {
+      Some(r) => r,
+      None => return err("beryllium: invalid destination stretch rect."),
+    };
+    if s.w == 0 || s.h == 0 || d.w == 0 || d.h == 0 {
+      return Ok(());
+    }
+    let scale_x = s.w as f32 / d.w as f32;
+    let scale_y = s.h as f32 / d.h as f32;
+    let src_pitch = self.pitch();
+    let mut lock = dst.lock()?;
+    let dst_pitch = lock.pitch();
+    let out = lock.pixels_mut();
+    self.with_pixels(|src| {
+      let at = |x: usize, y: usize| {
+        (s.y as usize + y) * src_pitch + (s.x as usize + x) * 4
+      };
+      for dy in 0..d.h as usize {
+        let (y0, y1, fy) = linear_sample(dy, scale_y, s.h as usize);
+        for dx in 0..d.w as usize {
+          let (x0, x1, fx) = linear_sample(dx, scale_x, s.w as usize);
+          let o = (d.y as usize + dy) * dst_pitch + (d.x as usize + dx) * 4;
+          for c in 0..4 {
+            let lerp = |a: u8, b: u8, t: f32| {
+              f32::from(a) + (f32::from(b) - f32::from(a)) * t
+            };
+            let top = lerp(src[at(x0, y0) + c], src[at(x1, y0) + c], fx);
+            let bottom = lerp(src[at(x0, y1) + c], src[at(x1, y1) + c], fx);
+            out[o + c] = (top + (bottom - top) * fy + 0.5) as u8;
+          }
+        }
+      }
+    })
+  }
+
+  /// Sets (or clears) the clip rect of the surface.
+  ///
+  /// Blits onto this surface only draw inside the clip rect. `None` sets it
+  /// back to the whole surface.
+  ///
+  /// Returns `false` if the rect doesn't overlap the surface at all, in which
+  /// case nothing will be drawn by blits until it's changed.
+  pub fn set_clip_rect(&mut self, clip: Option<Rect>) -> bool {
+    let clip: Option<SDL_Rect> = clip.map(Into::into);
+    let ret =
+      unsafe { fermium::SDL_SetClipRect(self.nn.as_ptr(), opt_ptr(&clip)) };
+    ret != 0
+  }
+
+  /// Gets the clip rect of the surface.
+  pub fn clip_rect(&self) -> Rect {
+    let mut clip = SDL_Rect::default();
+    unsafe { fermium::SDL_GetClipRect(self.nn.as_ptr(), &mut clip) };
+    clip.into()
+  }
+
+  /// Sets (or clears) the transparent color of the surface.
+  ///
+  /// Pixels of the key color are skipped when blitting, and become fully
+  /// transparent when the surface is made into a texture. The color is mapped
+  /// to the closest color the surface's format can store.
+  pub fn set_color_key(
+    &mut self, key: Option<[u8; 4]>,
+  ) -> Result<(), SdlError> {
+    let (flag, pixel) = match key {
+      Some([r, g, b, a]) => (true, unsafe {
+        fermium::SDL_MapRGBA((*self.nn.as_ptr()).format, r, g, b, a)
+      }),
+      None => (false, 0),
+    };
+    let ret =
+      unsafe { fermium::SDL_SetColorKey(self.nn.as_ptr(), flag as _, pixel) };
     if ret >= 0 {
-      Ok(SurfaceLock { surface: self })
+      Ok(())
     } else {
       Err(sdl_get_error())
     }
   }
 
+  /// Gets the transparent color of the surface, if any.
+  pub fn color_key(&self) -> Option<[u8; 4]> {
+    let mut pixel = 0;
+    let ret = unsafe { fermium::SDL_GetColorKey(self.nn.as_ptr(), &mut pixel) };
+    if ret >= 0 {
+      let [mut r, mut g, mut b, mut a] = [0; 4];
+      unsafe {
+        fermium::SDL_GetRGBA(
+          pixel,
+          (*self.nn.as_ptr()).format,
+          &mut r,
+          &mut g,
+          &mut b,
+          &mut a,
+        )
+      };
+      Some([r, g, b, a])
+    } else {
+      None
+    }
+  }
+
+  /// Sets an alpha value that's multiplied into every pixel during a blit.
+  pub fn set_alpha_mod(&mut self, alpha: u8) -> Result<(), SdlError> {
+    let ret =
+      unsafe { fermium::SDL_SetSurfaceAlphaMod(self.nn.as_ptr(), alpha) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Gets the alpha value that's multiplied in during a blit.
+  pub fn alpha_mod(&self) -> Result<u8, SdlError> {
+    let mut alpha = 0;
+    let ret =
+      unsafe { fermium::SDL_GetSurfaceAlphaMod(self.nn.as_ptr(), &mut alpha) };
+    if ret >= 0 {
+      Ok(alpha)
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Sets a color that's multiplied into every pixel during a blit.
+  ///
+  /// `[255, 255, 255]` leaves the colors alone.
+  pub fn set_color_mod(&mut self, [r, g, b]: [u8; 3]) -> Result<(), SdlError> {
+    let ret =
+      unsafe { fermium::SDL_SetSurfaceColorMod(self.nn.as_ptr(), r, g, b) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Gets the color that's multiplied in during a blit.
+  pub fn color_mod(&self) -> Result<[u8; 3], SdlError> {
+    let [mut r, mut g, mut b] = [0; 3];
+    let ret = unsafe {
+      fermium::SDL_GetSurfaceColorMod(self.nn.as_ptr(), &mut r, &mut g, &mut b)
+    };
+    if ret >= 0 {
+      Ok([r, g, b])
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Sets how this surface combines with the destination when blitted.
+  pub fn set_blend_mode(&mut self, mode: BlendMode) -> Result<(), SdlError> {
+    let ret =
+      unsafe { fermium::SDL_SetSurfaceBlendMode(self.nn.as_ptr(), mode as _) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Gets how this surface combines with the destination when blitted.
+  pub fn blend_mode(&self) -> Result<BlendMode, SdlError> {
+    let mut mode = 0;
+    let ret =
+      unsafe { fermium::SDL_GetSurfaceBlendMode(self.nn.as_ptr(), &mut mode) };
+    if ret >= 0 {
+      BlendMode::try_from(mode).map_err(|()| {
+        SdlError(Box::new(alloc::format!("Unknown blend mode: {}", mode)))
+      })
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Turns RLE acceleration on or off.
+  ///
+  /// RLE makes blitting a color keyed surface much faster, at the cost of the
+  /// surface having to be decoded whenever it's locked. The encoding happens
+  /// lazily, on the next blit.
+  pub fn set_rle(&mut self, enabled: bool) -> Result<(), SdlError> {
+    let ret =
+      unsafe { fermium::SDL_SetSurfaceRLE(self.nn.as_ptr(), enabled as _) };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// If the surface is currently RLE encoded.
+  ///
+  /// Since encoding is lazy, this stays `false` after
+  /// [`set_rle`](Self::set_rle) until the surface is first blitted.
+  pub fn has_rle(&self) -> bool {
+    unsafe { (*self.nn.as_ptr()).flags & fermium::SDL_RLEACCEL != 0 }
+  }
+
+  /// Sets the palette for an indexed surface.
+  ///
+  /// The surface shares the palette rather than copying it, so any later color
+  /// changes to the palette also affect the surface. It's fine to drop your
+  /// `Palette` afterwards, the surface keeps its own reference.
+  ///
+  /// Indexed surfaces are created with a palette of the right size already,
+  /// which can be edited through `pixel_format().palette()` instead.
+  pub fn set_palette(&mut self, palette: &Palette) -> Result<(), SdlError> {
+    let ret = unsafe {
+      fermium::SDL_SetSurfacePalette(self.nn.as_ptr(), palette.nn.as_ptr())
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Reads the color of one pixel.
+  ///
+  /// This is slow compared to working on the locked pixels directly, but it
+  /// handles the pitch, byte order, and format (including palettes) for you.
+  ///
+  /// ## Failure
+  /// * Formats with less than 8 bits per pixel aren't supported.
+  ///
+  /// ## Panics
+  /// * If the position is outside the surface.
+  pub fn read_pixel(&self, x: usize, y: usize) -> Result<[u8; 4], SdlError> {
+    let start = self.pixel_offset(x, y)?;
+    let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
+    let value = self.with_pixels(|pixels| {
+      pixel_value(&pixels[start..start + bytes_per_pixel])
+    })?;
+    let [mut r, mut g, mut b, mut a] = [0; 4];
+    unsafe {
+      fermium::SDL_GetRGBA(
+        value,
+        (*self.nn.as_ptr()).format,
+        &mut r,
+        &mut g,
+        &mut b,
+        &mut a,
+      )
+    };
+    Ok([r, g, b, a])
+  }
+
+  /// Writes the color of one pixel.
+  ///
+  /// The color is mapped to the closest color the format can store.
+  ///
+  /// ## Failure
+  /// * Formats with less than 8 bits per pixel aren't supported.
+  ///
+  /// ## Panics
+  /// * If the position is outside the surface.
+  pub fn write_pixel(
+    &mut self, x: usize, y: usize, [r, g, b, a]: [u8; 4],
+  ) -> Result<(), SdlError> {
+    let start = self.pixel_offset(x, y)?;
+    let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
+    let value =
+      unsafe { fermium::SDL_MapRGBA((*self.nn.as_ptr()).format, r, g, b, a) };
+    let mut lock = self.lock()?;
+    set_pixel_value(
+      &mut lock.pixels_mut()[start..start + bytes_per_pixel],
+      value,
+    );
+    Ok(())
+  }
+
+  fn pixel_offset(&self, x: usize, y: usize) -> Result<usize, SdlError> {
+    assert!(x < self.width());
+    assert!(y < self.height());
+    let format = self.pixel_format();
+    if format.bits_per_pixel() < 8 {
+      return Err(SdlError(Box::new(String::from(
+        "beryllium: can't address pixels smaller than a byte.",
+      ))));
+    }
+    Ok(self.pitch() * y + x * format.bytes_per_pixel())
+  }
+
+  /// Calls `f` with the surface's pixel bytes, locking the surface around the
+  /// call if it needs it.
+  ///
+  /// This only needs `&self`, since a `Surface` can't be shared between
+  /// threads and the pixels are only read.
+  pub(crate) fn with_pixels<R>(
+    &self, f: impl FnOnce(&[u8]) -> R,
+  ) -> Result<R, SdlError> {
+    let p = self.nn.as_ptr();
+    let must_lock = unsafe { fermium::SDL_MUSTLOCK(p) };
+    if must_lock && unsafe { fermium::SDL_LockSurface(p) } < 0 {
+      return Err(sdl_get_error());
+    }
+    let len = self.pitch() * self.height();
+    let pixels = unsafe { (*p).pixels as *const u8 };
+    // SDL leaves the pixels null for a surface with no area.
+    let out = if pixels.is_null() || len == 0 {
+      f(&[])
+    } else {
+      f(unsafe { core::slice::from_raw_parts(pixels, len) })
+    };
+    if must_lock {
+      unsafe { fermium::SDL_UnlockSurface(p) }
+    }
+    Ok(out)
+  }
+
+  /// Locks the surface so that its pixels can be accessed directly.
+  ///
+  /// SDL only actually needs to lock surfaces that have RLE acceleration
+  /// applied (see `SDL_MUSTLOCK`), so for other surfaces this is free. The
+  /// surface unlocks when the lock guard drops.
+  pub fn lock(&mut self) -> Result<SurfaceLock<'_>, SdlError> {
+    let must_lock = unsafe { fermium::SDL_MUSTLOCK(self.nn.as_ptr()) };
+    if must_lock {
+      let ret = unsafe { fermium::SDL_LockSurface(self.nn.as_ptr()) };
+      if ret < 0 {
+        return Err(sdl_get_error());
+      }
+    }
+    Ok(SurfaceLock { surface: self, must_unlock: must_lock })
+  }
+
+  /// The surface's pixel format.
   pub fn pixel_format(&self) -> &PixelFormat {
+    // Note: `PixelFormat` is a transparent `NonNull`, and a surface's format
+    // pointer is never null, so the field itself can be viewed as one.
     unsafe {
-      let f: *const SDL_PixelFormat = (*self.nn.as_ptr()).format;
-      assert!(!f.is_null());
-      core::mem::transmute::<&*const SDL_PixelFormat, &PixelFormat>(&f)
+      let f: *const *mut SDL_PixelFormat = &(*self.nn.as_ptr()).format;
+      assert!(!(*f).is_null());
+      &*f.cast::<PixelFormat>()
     }
   }
 
+  /// The surface's pixel format, as a format enum.
+  pub fn pixel_format_enum(&self) -> PixelFormatEnum {
+    self.pixel_format().pixel_format_enum()
+  }
+
+  /// Multiplies the color channels of every pixel by its alpha, in place.
+  ///
+  /// Art that's stored premultiplied composites correctly when scaled or
+  /// filtered, but it then needs a premultiplied blend mode. SDL 2.0.12 has no
+  /// `SDL_PremultiplyAlpha`, so this is done in software.
+  ///
+  /// ## Failure
+  /// * The format must have an alpha channel and 2 or 4 bytes per pixel.
+  pub fn premultiply_alpha(&mut self) -> Result<(), SdlError> {
+    let format = self.pixel_format();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let a_mask = format.a_mask();
+    if a_mask == 0 || !(bytes_per_pixel == 2 || bytes_per_pixel == 4) {
+      return Err(SdlError(Box::new(String::from(
+        "beryllium: can only premultiply 16 or 32 bit formats with alpha.",
+      ))));
+    }
+    let masks = [format.r_mask(), format.g_mask(), format.b_mask()];
+    let a_shift = a_mask.trailing_zeros();
+    let a_max = a_mask >> a_shift;
+    let (width, height) = (self.width(), self.height());
+    let mut lock = self.lock()?;
+    let pitch = lock.pitch();
+    let pixels = lock.pixels_mut();
+    for y in 0..height {
+      let row = &mut pixels[y * pitch..][..width * bytes_per_pixel];
+      for px in row.chunks_exact_mut(bytes_per_pixel) {
+        let value = if bytes_per_pixel == 4 {
+          u32::from_ne_bytes([px[0], px[1], px[2], px[3]])
+        } else {
+          u32::from(u16::from_ne_bytes([px[0], px[1]]))
+        };
+        let a = (value & a_mask) >> a_shift;
+        let mut out = value & a_mask;
+        for &mask in masks.iter() {
+          let shift = mask.trailing_zeros() % 32;
+          let c = (value & mask) >> shift;
+          let c = (c * a + a_max / 2) / a_max;
+          out |= (c << shift) & mask;
+        }
+        if bytes_per_pixel == 4 {
+          px.copy_from_slice(&out.to_ne_bytes());
+        } else {
+          px.copy_from_slice(&(out as u16).to_ne_bytes());
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Makes a copy of this surface that uses a different pixel format.
+  pub fn convert_format(
+    &self, format: PixelFormatEnum,
+  ) -> Result<Surface, SdlError> {
+    NonNull::new(unsafe {
+      fermium::SDL_ConvertSurfaceFormat(self.nn.as_ptr(), format.0, 0)
+    })
+    .ok_or_else(sdl_get_error)
+    .map(|nn| Surface { nn })
+  }
+
+  /// Makes a flipped copy of the surface.
+  ///
+  /// ## Failure
+  /// * Formats with less than 8 bits per pixel aren't supported.
+  pub fn flipped(&self, flip: FlipMode) -> Result<Surface, SdlError> {
+    let (w, h) = (self.width(), self.height());
+    let (flip_x, flip_y) = match flip {
+      FlipMode::None => (false, false),
+      FlipMode::Horizontal => (true, false),
+      FlipMode::Vertical => (false, true),
+      FlipMode::Both => (true, true),
+    };
+    self.remapped(w, h, |x, y| {
+      (if flip_x { w - 1 - x } else { x }, if flip_y { h - 1 - y } else { y })
+    })
+  }
+
+  /// Makes a copy of the surface rotated clockwise by `n` quarter turns.
+  ///
+  /// ## Failure
+  /// * Formats with less than 8 bits per pixel aren't supported.
+  pub fn rotated_90(&self, n: u32) -> Result<Surface, SdlError> {
+    let (w, h) = (self.width(), self.height());
+    match n % 4 {
+      0 => self.remapped(w, h, |x, y| (x, y)),
+      1 => self.remapped(h, w, |x, y| (y, h - 1 - x)),
+      2 => self.remapped(w, h, |x, y| (w - 1 - x, h - 1 - y)),
+      _ => self.remapped(h, w, |x, y| (w - 1 - y, x)),
+    }
+  }
+
+  /// Makes a new surface of the same format (and palette and color key), where
+  /// each output pixel is copied from the source position `f(x, y)` gives.
+  fn remapped(
+    &self, width: usize, height: usize,
+    f: impl Fn(usize, usize) -> (usize, usize),
+  ) -> Result<Surface, SdlError> {
+    let format = self.pixel_format();
+    let bits_per_pixel = format.bits_per_pixel();
+    if bits_per_pixel < 8 {
+      return Err(SdlError(Box::new(String::from(
+        "beryllium: can't remap pixels smaller than a byte.",
+      ))));
+    }
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut out = Surface::new_with_format(
+      width,
+      height,
+      bits_per_pixel,
+      format.pixel_format_enum(),
+    )?;
+    if let Some(palette) = format.palette() {
+      out.set_palette(palette)?;
+    }
+    if let Some(key) = self.color_key() {
+      out.set_color_key(Some(key))?;
+    }
+    let src_pitch = self.pitch();
+    let mut lock = out.lock()?;
+    let dst_pitch = lock.pitch();
+    let dst = lock.pixels_mut();
+    self.with_pixels(|src| {
+      for y in 0..height {
+        for x in 0..width {
+          let (src_x, src_y) = f(x, y);
+          let s = src_y * src_pitch + src_x * bytes_per_pixel;
+          let d = y * dst_pitch + x * bytes_per_pixel;
+          dst[d..d + bytes_per_pixel]
+            .copy_from_slice(&src[s..s + bytes_per_pixel]);
+        }
+      }
+    })?;
+    drop(lock);
+    Ok(out)
+  }
+
+  /// Copies the surface into an [`image::RgbaImage`].
+  ///
+  /// The surface is converted to RGBA if it's in some other format.
+  #[cfg(feature = "image")]
+  pub fn to_rgba_image(&self) -> Result<image::RgbaImage, SdlError> {
+    let mut rgba = self.convert_format(PixelFormatEnum::RGBA32)?;
+    let (width, height) = (rgba.width(), rgba.height());
+    let row_bytes = width * 4;
+    let lock = rgba.lock()?;
+    let mut buf = Vec::with_capacity(row_bytes * height);
+    for row in lock.pixels().chunks(lock.pitch().max(1)).take(height) {
+      buf.extend_from_slice(&row[..row_bytes]);
+    }
+    Ok(image::RgbaImage::from_raw(width as u32, height as u32, buf).unwrap())
+  }
+
+  /// Makes a new RGBA surface with a copy of the image's pixels.
+  #[cfg(feature = "image")]
+  pub fn from_rgba_image(img: &image::RgbaImage) -> Result<Self, SdlError> {
+    let (width, height) = (img.width() as usize, img.height() as usize);
+    let row_bytes = width * 4;
+    let mut surface =
+      Surface::new_with_format(width, height, 32, PixelFormatEnum::RGBA32)?;
+    let mut lock = surface.lock()?;
+    let pitch = lock.pitch().max(1);
+    let src_rows = img.as_raw().chunks(row_bytes.max(1));
+    for (dst, src) in lock.pixels_mut().chunks_mut(pitch).zip(src_rows) {
+      dst[..row_bytes].copy_from_slice(src);
+    }
+    drop(lock);
+    Ok(surface)
+  }
+
   /// Width in pixels
   pub fn width(&self) -> usize {
     unsafe { (*self.nn.as_ptr()).w as usize }
@@ -108,17 +748,71 @@ impl Surface {
     unsafe { (*self.nn.as_ptr()).h as usize }
   }
   /// Pitch between row starts, in bytes.
-  pub fn pitch(&self) -> isize {
-    unsafe { (*self.nn.as_ptr()).h as isize }
+  pub fn pitch(&self) -> usize {
+    unsafe { (*self.nn.as_ptr()).pitch as usize }
+  }
+}
+
+/// A pixel's value from its 1 to 4 bytes, the same way SDL reads them.
+fn pixel_value(bytes: &[u8]) -> u32 {
+  let mut buf = [0; 4];
+  if cfg!(target_endian = "little") {
+    buf[..bytes.len()].copy_from_slice(bytes);
+  } else {
+    buf[4 - bytes.len()..].copy_from_slice(bytes);
+  }
+  u32::from_ne_bytes(buf)
+}
+
+/// Stores a pixel's value into its 1 to 4 bytes, the same way SDL writes them.
+fn set_pixel_value(bytes: &mut [u8], value: u32) {
+  let buf = value.to_ne_bytes();
+  let len = bytes.len();
+  if cfg!(target_endian = "little") {
+    bytes.copy_from_slice(&buf[..len]);
+  } else {
+    bytes.copy_from_slice(&buf[4 - len..]);
+  }
+}
+
+/// The rect (or the whole surface if `None`), if it's entirely within the
+/// surface.
+fn rect_within(rect: Option<Rect>, surface: &Surface) -> Option<Rect> {
+  let (w, h) = (surface.width() as i64, surface.height() as i64);
+  let r = rect.unwrap_or_else(|| Rect::new(0, 0, w as u32, h as u32));
+  let inside = r.x >= 0
+    && r.y >= 0
+    && r.x as i64 + r.w as i64 <= w
+    && r.y as i64 + r.h as i64 <= h;
+  if inside {
+    Some(r)
+  } else {
+    None
   }
 }
 
+/// The two source indexes to blend for output index `i`, and how far to go
+/// from the first to the second.
+fn linear_sample(i: usize, scale: f32, len: usize) -> (usize, usize, f32) {
+  let pos = ((i as f32 + 0.5) * scale - 0.5).max(0.0);
+  let i0 = (pos as usize).min(len - 1);
+  let i1 = (i0 + 1).min(len - 1);
+  (i0, i1, pos - i0 as f32)
+}
+
+/// A lock on a surface's pixels.
+///
+/// Derefs to the [`Surface`], so `width`, `height`, `pitch`, and so on are all
+/// available while locked.
 pub struct SurfaceLock<'s> {
   surface: &'s mut Surface,
+  must_unlock: bool,
 }
 impl<'s> Drop for SurfaceLock<'s> {
   fn drop(&mut self) {
-    unsafe { fermium::SDL_UnlockSurface(self.surface.nn.as_ptr()) }
+    if self.must_unlock {
+      unsafe { fermium::SDL_UnlockSurface(self.surface.nn.as_ptr()) }
+    }
   }
 }
 impl<'s> Deref for SurfaceLock<'s> {
@@ -130,35 +824,46 @@ impl<'s> Deref for SurfaceLock<'s> {
 impl<'s> Index<(usize, usize)> for SurfaceLock<'s> {
   type Output = [u8];
   fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-    unsafe {
-      assert!(x < self.width());
-      assert!(y < self.height());
-      let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
-      let base = self.pixels();
-      let row_start = base.offset(self.pitch() * (y as isize));
-      let pixel = row_start.add(x * bytes_per_pixel);
-      core::slice::from_raw_parts(pixel, bytes_per_pixel)
-    }
+    assert!(x < self.width());
+    assert!(y < self.height());
+    let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
+    let start = self.pitch() * y + x * bytes_per_pixel;
+    &self.pixels()[start..start + bytes_per_pixel]
   }
 }
 impl<'s> IndexMut<(usize, usize)> for SurfaceLock<'s> {
   fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-    unsafe {
-      assert!(x < self.width());
-      assert!(y < self.height());
-      let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
-      let base = self.pixels_mut();
-      let row_start = base.offset(self.pitch() * (y as isize));
-      let pixel = row_start.add(x * bytes_per_pixel);
-      core::slice::from_raw_parts_mut(pixel, bytes_per_pixel)
-    }
+    assert!(x < self.width());
+    assert!(y < self.height());
+    let bytes_per_pixel = self.pixel_format().bytes_per_pixel();
+    let start = self.pitch() * y + x * bytes_per_pixel;
+    &mut self.pixels_mut()[start..start + bytes_per_pixel]
   }
 }
 impl<'s> SurfaceLock<'s> {
-  fn pixels(&self) -> *const u8 {
-    unsafe { (*self.surface.nn.as_ptr()).pixels as *const u8 }
+  /// All of the surface's pixel bytes, `pitch * height` long.
+  ///
+  /// Rows can have padding at the end, so use the pitch to find where each
+  /// row starts.
+  pub fn pixels(&self) -> &[u8] {
+    let len = self.pitch() * self.height();
+    let p = unsafe { (*self.surface.nn.as_ptr()).pixels as *const u8 };
+    // SDL leaves the pixels null for a surface with no area.
+    if p.is_null() || len == 0 {
+      &[]
+    } else {
+      unsafe { core::slice::from_raw_parts(p, len) }
+    }
   }
-  fn pixels_mut(&mut self) -> *mut u8 {
-    unsafe { (*self.surface.nn.as_ptr()).pixels as *mut u8 }
+
+  /// As [`pixels`](Self::pixels), but mutable.
+  pub fn pixels_mut(&mut self) -> &mut [u8] {
+    let len = self.pitch() * self.height();
+    let p = unsafe { (*self.surface.nn.as_ptr()).pixels as *mut u8 };
+    if p.is_null() || len == 0 {
+      &mut []
+    } else {
+      unsafe { core::slice::from_raw_parts_mut(p, len) }
+    }
   }
 }
@@ -0,0 +1,114 @@
+use alloc::string::String;
+
+use crate::{Event, WindowEvent};
+
+/// Tracks IME composition, combining text editing and text input events into
+/// "the text being composed" and "the text that's been committed".
+///
+/// Pass every event to [`handle_event`](TextComposition::handle_event). Then
+/// draw the composition (with its cursor) where the user is typing, and take
+/// the committed text to add to your text field.
+///
+/// * Each editing event replaces the whole composition, and committed text
+///   ends it.
+/// * Losing window focus drops the composition, since the IME does as well.
+/// * SDL 2.0.12 (which beryllium uses) cuts editing text off at 31 bytes, so
+///   very long compositions won't be complete. Later versions of SDL add a
+///   separate event for long compositions, but it's not available here.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextComposition {
+  composition: String,
+  cursor: usize,
+  selection_len: usize,
+  committed: String,
+}
+impl TextComposition {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Updates the state from an event.
+  ///
+  /// Gives `true` if the event was for text, so you can skip your own
+  /// handling of it.
+  pub fn handle_event(&mut self, event: &Event) -> bool {
+    match event {
+      Event::TextEditing(editing) => {
+        self.composition.clear();
+        self.composition.push_str(&editing.text);
+        self.cursor = editing.start.max(0) as usize;
+        self.selection_len = editing.length.max(0) as usize;
+        true
+      }
+      Event::TextInput(input) => {
+        self.clear_composition();
+        self.committed.push_str(&input.text);
+        true
+      }
+      Event::Window(WindowEvent::FocusLost { .. }) => {
+        self.clear_composition();
+        false
+      }
+      _ => false,
+    }
+  }
+
+  /// The text that's being composed, which isn't part of the text field yet.
+  pub fn composition(&self) -> &str {
+    &self.composition
+  }
+
+  /// If there's any text being composed.
+  ///
+  /// While this is true, keys such as Enter and Backspace go to the IME, so
+  /// you probably want to ignore them.
+  pub fn is_composing(&self) -> bool {
+    !self.composition.is_empty()
+  }
+
+  /// Where the cursor is within the composition, in characters.
+  pub fn cursor(&self) -> usize {
+    self.cursor
+  }
+
+  /// How many characters after the cursor are selected.
+  pub fn selection_len(&self) -> usize {
+    self.selection_len
+  }
+
+  /// The byte range of the composition that's selected (or just the cursor
+  /// position, if nothing is selected).
+  ///
+  /// This is clamped to the composition, in case SDL cut it off.
+  pub fn selection_byte_range(&self) -> core::ops::Range<usize> {
+    let byte_index = |chars: usize| {
+      self
+        .composition
+        .char_indices()
+        .nth(chars)
+        .map_or(self.composition.len(), |(i, _)| i)
+    };
+    let start = byte_index(self.cursor);
+    let end = byte_index(self.cursor.saturating_add(self.selection_len));
+    start..end
+  }
+
+  /// Text that's been committed but not yet taken.
+  pub fn committed(&self) -> &str {
+    &self.committed
+  }
+
+  /// Takes the committed text, to add it to your text field.
+  pub fn take_committed(&mut self) -> String {
+    core::mem::take(&mut self.committed)
+  }
+
+  /// Drops the composition, such as when the text field loses focus.
+  ///
+  /// This doesn't tell the IME. Stopping text input does that.
+  pub fn clear_composition(&mut self) {
+    self.composition.clear();
+    self.cursor = 0;
+    self.selection_len = 0;
+  }
+}
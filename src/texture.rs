@@ -1,20 +1,265 @@
-use core::ptr::NonNull;
+use core::{convert::TryInto, ptr::NonNull};
 
-use alloc::rc::Rc;
+use alloc::{boxed::Box, rc::Rc, string::String};
 
-use fermium::SDL_Texture;
+use fermium::{SDL_Rect, SDL_Texture};
 
-use crate::Renderer;
+use crate::{
+  sdl_get_error, yuv_frame_len, PixelFormatEnum, Rect, Renderer, SdlError,
+};
 
+/// An image stored on the GPU, for a renderer to draw.
+///
+/// The pixels can be lost even while the texture lives: after an
+/// [`Event::RenderDeviceReset`](crate::Event::RenderDeviceReset) you must
+/// upload all of them again.
 pub struct Texture {
   pub(crate) nn: NonNull<SDL_Texture>,
   // Note(Lokathor): As long as the texture lives, we have to also keep the
   // renderer that created it alive.
   #[allow(dead_code)]
   pub(crate) rend: Rc<Renderer>,
+  /// Estimated size of the pixel data, for the renderer's stats.
+  pub(crate) bytes: usize,
+}
+impl core::fmt::Debug for Texture {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "Texture({:p})", self.nn.as_ptr())
+  }
+}
+impl PartialEq for Texture {
+  /// Textures are equal when they're the same texture.
+  fn eq(&self, other: &Self) -> bool {
+    self.nn == other.nn
+  }
 }
 impl Drop for Texture {
   fn drop(&mut self) {
     unsafe { fermium::SDL_DestroyTexture(self.nn.as_ptr()) }
+    self.rend.counters.remove_texture_bytes(self.bytes);
+  }
+}
+impl Texture {
+  /// Width and height in pixels.
+  pub fn size(&self) -> Result<[u32; 2], SdlError> {
+    let mut w = 0;
+    let mut h = 0;
+    let ret = unsafe {
+      fermium::SDL_QueryTexture(
+        self.nn.as_ptr(),
+        core::ptr::null_mut(),
+        core::ptr::null_mut(),
+        &mut w,
+        &mut h,
+      )
+    };
+    if ret >= 0 {
+      Ok([w as u32, h as u32])
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// The format of the texture's pixel data.
+  pub fn pixel_format(&self) -> Result<PixelFormatEnum, SdlError> {
+    let mut format = 0;
+    let ret = unsafe {
+      fermium::SDL_QueryTexture(
+        self.nn.as_ptr(),
+        &mut format,
+        core::ptr::null_mut(),
+        core::ptr::null_mut(),
+        core::ptr::null_mut(),
+      )
+    };
+    if ret >= 0 {
+      Ok(PixelFormatEnum(format))
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Checks that a rect is inside the texture, giving its width and height
+  /// (the whole texture for `None`).
+  ///
+  /// SDL doesn't check lock and update rects itself.
+  fn checked_rect_size(
+    &self, rect: Option<Rect>,
+  ) -> Result<[usize; 2], SdlError> {
+    let [width, height] = self.size()?;
+    match rect {
+      None => Ok([width as usize, height as usize]),
+      Some(r) => {
+        if r.x >= 0
+          && r.y >= 0
+          && i64::from(r.x) + i64::from(r.w) <= i64::from(width)
+          && i64::from(r.y) + i64::from(r.h) <= i64::from(height)
+        {
+          Ok([r.w as usize, r.h as usize])
+        } else {
+          Err(SdlError(Box::new(String::from(
+            "beryllium: the rect must be inside the texture",
+          ))))
+        }
+      }
+    }
+  }
+}
+
+impl Texture {
+  /// Locks all or part of a streaming texture for write-only access.
+  ///
+  /// * `rect` of `None` locks the whole texture.
+  /// * The bytes you get are **not** necessarily the current texture content,
+  ///   so overwrite every pixel you care about.
+  /// * The changes are uploaded when the lock guard drops.
+  /// * For planar YUV formats (`YV12`, `IYUV`, `NV12`, `NV21`) the bytes are
+  ///   the whole frame, laid out as described by [`yuv_frame_len`].
+  ///
+  /// ## Failure
+  /// * Only textures made with [`TextureAccess::Streaming`] can be locked.
+  /// * The rect must be inside the texture.
+  /// * Planar YUV textures can only be locked whole.
+  ///
+  /// [`TextureAccess::Streaming`]: crate::TextureAccess::Streaming
+  pub fn lock(
+    &mut self, rect: Option<Rect>,
+  ) -> Result<TextureLock<'_>, SdlError> {
+    let rect_size = self.checked_rect_size(rect)?;
+    let format = self.pixel_format()?;
+    if is_planar_yuv(format) && rect.is_some() {
+      let [width, height] = self.size()?;
+      if rect_size != [width as usize, height as usize] {
+        return Err(SdlError(Box::new(String::from(
+          "beryllium: planar YUV textures can only be locked whole",
+        ))));
+      }
+    }
+    let sdl_rect: Option<SDL_Rect> = rect.map(Into::into);
+    let rect_ptr: *const SDL_Rect = match sdl_rect.as_ref() {
+      Some(r) => r,
+      None => core::ptr::null(),
+    };
+    let mut pixels = core::ptr::null_mut();
+    let mut pitch = 0;
+    let ret = unsafe {
+      fermium::SDL_LockTexture(
+        self.nn.as_ptr(),
+        rect_ptr,
+        &mut pixels,
+        &mut pitch,
+      )
+    };
+    if ret >= 0 {
+      let len = area_len(format, rect_size, pitch as usize);
+      Ok(TextureLock {
+        texture: self,
+        pixels: pixels.cast(),
+        pitch: pitch as usize,
+        len,
+      })
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+
+  /// Replaces all or part of the texture with new pixel data.
+  ///
+  /// * `pitch` is the number of bytes between the start of each row in
+  ///   `pixels`.
+  /// * For planar YUV formats, `pixels` holds every plane of the rect, laid
+  ///   out as described by [`yuv_frame_len`], and `pitch` is the Y plane's.
+  ///
+  /// This is slow, and meant for static textures. Use [`lock`](Self::lock) for
+  /// streaming textures.
+  ///
+  /// ## Failure
+  /// * The rect must be inside the texture.
+  ///
+  /// ## Panics
+  /// * If `pixels` is too short for the rect at the given pitch.
+  pub fn update(
+    &mut self, rect: Option<Rect>, pixels: &[u8], pitch: usize,
+  ) -> Result<(), SdlError> {
+    let rect_size = self.checked_rect_size(rect)?;
+    let format = self.pixel_format()?;
+    assert!(pixels.len() >= area_len(format, rect_size, pitch));
+    let sdl_rect: Option<SDL_Rect> = rect.map(Into::into);
+    let rect_ptr: *const SDL_Rect = match sdl_rect.as_ref() {
+      Some(r) => r,
+      None => core::ptr::null(),
+    };
+    let ret = unsafe {
+      fermium::SDL_UpdateTexture(
+        self.nn.as_ptr(),
+        rect_ptr,
+        pixels.as_ptr().cast(),
+        pitch.try_into().unwrap(),
+      )
+    };
+    if ret >= 0 {
+      Ok(())
+    } else {
+      Err(sdl_get_error())
+    }
+  }
+}
+
+/// A lock on a streaming texture's pixels.
+///
+/// The texture unlocks (and uploads the new pixels) when this drops.
+pub struct TextureLock<'t> {
+  texture: &'t mut Texture,
+  pixels: *mut u8,
+  pitch: usize,
+  len: usize,
+}
+impl<'t> Drop for TextureLock<'t> {
+  fn drop(&mut self) {
+    unsafe { fermium::SDL_UnlockTexture(self.texture.nn.as_ptr()) }
+  }
+}
+impl<'t> TextureLock<'t> {
+  /// Bytes between the start of each row.
+  pub fn pitch(&self) -> usize {
+    self.pitch
+  }
+
+  /// The locked pixel bytes.
+  ///
+  /// Each row starts `pitch` bytes after the one before, and the slice ends
+  /// at the end of the last row of the rect (so it's a little shorter than
+  /// `pitch * height`). Planar YUV textures give the whole frame instead.
+  pub fn pixels_mut(&mut self) -> &mut [u8] {
+    unsafe { core::slice::from_raw_parts_mut(self.pixels, self.len) }
+  }
+}
+
+fn is_planar_yuv(format: PixelFormatEnum) -> bool {
+  matches!(
+    format,
+    PixelFormatEnum::YV12
+      | PixelFormatEnum::IYUV
+      | PixelFormatEnum::NV12
+      | PixelFormatEnum::NV21
+  )
+}
+
+/// The bytes from the start of the first row to the end of the last, for an
+/// area of `[width, height]` pixels with `pitch` bytes between rows.
+///
+/// * SDL gives a pointer to the rect's first pixel, not to the start of the
+///   row, so the last row is only as long as the rect.
+/// * Planar YUV formats also have their chroma planes after the Y plane.
+fn area_len(
+  format: PixelFormatEnum, [width, height]: [usize; 2], pitch: usize,
+) -> usize {
+  if width == 0 || height == 0 {
+    0
+  } else if is_planar_yuv(format) {
+    yuv_frame_len(format, pitch, height).unwrap_or(0)
+  } else {
+    let bytes_per_pixel = fermium::SDL_BYTESPERPIXEL(format.0) as usize;
+    pitch * (height - 1) + width * bytes_per_pixel
   }
 }
@@ -0,0 +1,180 @@
+use alloc::vec::Vec;
+
+use crate::{Camera2D, Rect, RectF, RendererWindow, SdlError, Texture};
+
+/// Draws a grid of tiles from a tileset texture, skipping any that are off
+/// screen.
+///
+/// * The tileset is read left to right, top to bottom, so tile index `n` is at
+///   column `n % columns` and row `n / columns` of the texture.
+/// * Each cell of the map holds `Some(index)`, or `None` for an empty cell.
+/// * Only the cells that overlap the view are copied, so the cost of drawing is
+///   proportional to the screen size, not the map size. The copies are issued
+///   row by row from a single texture, which SDL's renderer batches together.
+pub struct TileMapRenderer<'t> {
+  tileset: &'t Texture,
+  tile_size: [u32; 2],
+  columns: u32,
+  map_size: [u32; 2],
+  tiles: Vec<Option<u32>>,
+}
+impl<'t> TileMapRenderer<'t> {
+  /// Makes a new map of the given size (in tiles), with every cell empty.
+  ///
+  /// ## Panics
+  /// * If either dimension of `tile_size` is 0.
+  pub fn new(
+    tileset: &'t Texture, tile_size: [u32; 2], map_size: [u32; 2],
+  ) -> Result<Self, SdlError> {
+    let len = (map_size[0] as usize) * (map_size[1] as usize);
+    let mut tiles = Vec::with_capacity(len);
+    tiles.resize(len, None);
+    Self::from_tiles(tileset, tile_size, map_size[0], tiles)
+  }
+
+  /// Makes a map from an existing row-major grid of tile indices.
+  ///
+  /// ## Panics
+  /// * If either dimension of `tile_size` is 0.
+  /// * If `tiles.len()` isn't a multiple of `map_width`.
+  pub fn from_tiles(
+    tileset: &'t Texture, tile_size: [u32; 2], map_width: u32,
+    tiles: Vec<Option<u32>>,
+  ) -> Result<Self, SdlError> {
+    assert!(tile_size[0] > 0 && tile_size[1] > 0);
+    let map_height = if map_width > 0 {
+      assert_eq!(tiles.len() % map_width as usize, 0);
+      (tiles.len() / map_width as usize) as u32
+    } else {
+      assert!(tiles.is_empty());
+      0
+    };
+    let [texture_w, _] = tileset.size()?;
+    let columns = (texture_w / tile_size[0]).max(1);
+    Ok(Self {
+      tileset,
+      tile_size,
+      columns,
+      map_size: [map_width, map_height],
+      tiles,
+    })
+  }
+
+  /// Width and height of the map, in tiles.
+  pub fn map_size(&self) -> [u32; 2] {
+    self.map_size
+  }
+
+  /// Width and height of a single tile, in pixels.
+  pub fn tile_size(&self) -> [u32; 2] {
+    self.tile_size
+  }
+
+  /// Gets the tile at a grid position, or `None` if the cell is empty or out
+  /// of bounds.
+  pub fn get(&self, x: u32, y: u32) -> Option<u32> {
+    self.cell_index(x, y).and_then(|i| self.tiles[i])
+  }
+
+  /// Sets the tile at a grid position.
+  ///
+  /// ## Panics
+  /// * If the position is out of bounds.
+  pub fn set(&mut self, x: u32, y: u32, tile: Option<u32>) {
+    let i = self.cell_index(x, y).expect("tile position out of bounds");
+    self.tiles[i] = tile;
+  }
+
+  /// Draws the map with its upper left corner at `origin` in screen space.
+  ///
+  /// The view that tiles are culled against is the renderer's logical size if
+  /// one is set, otherwise its output size.
+  pub fn draw(
+    &self, rend_win: &RendererWindow, origin: [i32; 2],
+  ) -> Result<(), SdlError> {
+    let [view_w, view_h] = match rend_win.logical_size() {
+      Some(size) => size,
+      None => rend_win.output_size()?,
+    };
+    let view = RectF::new(
+      -origin[0] as f32,
+      -origin[1] as f32,
+      view_w as f32,
+      view_h as f32,
+    );
+    let [tw, th] = self.tile_size;
+    self.for_each_visible(view, |x, y, src| {
+      let dst = Rect::new(
+        origin[0] + (x * tw) as i32,
+        origin[1] + (y * th) as i32,
+        tw,
+        th,
+      );
+      rend_win.copy(self.tileset, Some(src), Some(dst))
+    })
+  }
+
+  /// Draws the map through a camera.
+  ///
+  /// The map's upper left corner is at the world origin, and one world unit
+  /// is one tileset pixel.
+  pub fn draw_with_camera(
+    &self, rend_win: &RendererWindow, camera: &Camera2D,
+  ) -> Result<(), SdlError> {
+    let [tw, th] = self.tile_size;
+    self.for_each_visible(camera.visible_world_rect(), |x, y, src| {
+      let dst =
+        RectF::new((x * tw) as f32, (y * th) as f32, tw as f32, th as f32);
+      camera.copy(rend_win, self.tileset, Some(src), dst)
+    })
+  }
+
+  /// Calls `f` with the grid position and source rect of every non-empty tile
+  /// overlapping `view` (which is in map pixel space).
+  fn for_each_visible(
+    &self, view: RectF,
+    mut f: impl FnMut(u32, u32, Rect) -> Result<(), SdlError>,
+  ) -> Result<(), SdlError> {
+    let [tw, th] = [self.tile_size[0] as f32, self.tile_size[1] as f32];
+    let [map_w, map_h] = self.map_size;
+    let clamp = |v: f32, max: u32| -> u32 {
+      if v <= 0.0 {
+        0
+      } else if v >= max as f32 {
+        max
+      } else {
+        v as u32
+      }
+    };
+    let x_start = clamp(view.x / tw, map_w);
+    let y_start = clamp(view.y / th, map_h);
+    // Round the far edge up so that partly visible tiles are included.
+    let x_end = clamp((view.x + view.w) / tw + 1.0, map_w);
+    let y_end = clamp((view.y + view.h) / th + 1.0, map_h);
+    for y in y_start..y_end {
+      let row = (y as usize) * (map_w as usize);
+      for x in x_start..x_end {
+        if let Some(tile) = self.tiles[row + x as usize] {
+          f(x, y, self.src_rect(tile))?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn src_rect(&self, tile: u32) -> Rect {
+    let [tw, th] = self.tile_size;
+    let col = tile % self.columns;
+    let row = tile / self.columns;
+    Rect::new((col * tw) as i32, (row * th) as i32, tw, th)
+  }
+
+  fn cell_index(&self, x: u32, y: u32) -> Option<usize> {
+    let [map_w, map_h] = self.map_size;
+    if x < map_w && y < map_h {
+      Some((y as usize) * (map_w as usize) + (x as usize))
+    } else {
+      None
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use core::convert::TryFrom;
+
+use fermium::{
+  SDL_Finger, SDL_TouchDeviceType, SDL_TOUCH_DEVICE_DIRECT,
+  SDL_TOUCH_DEVICE_INDIRECT_ABSOLUTE, SDL_TOUCH_DEVICE_INDIRECT_RELATIVE,
+};
+
+use crate::FingerID;
+
+/// What sort of touch device a [`TouchID`](crate::TouchID) is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TouchDeviceType {
+  /// A touch screen, where positions are on the window.
+  Direct = SDL_TOUCH_DEVICE_DIRECT as _,
+  /// A trackpad that gives absolute positions on the pad.
+  IndirectAbsolute = SDL_TOUCH_DEVICE_INDIRECT_ABSOLUTE as _,
+  /// A trackpad that gives positions relative to where the touch started.
+  IndirectRelative = SDL_TOUCH_DEVICE_INDIRECT_RELATIVE as _,
+}
+impl TryFrom<SDL_TouchDeviceType> for TouchDeviceType {
+  type Error = ();
+  #[inline]
+  fn try_from(device_type: SDL_TouchDeviceType) -> Result<Self, Self::Error> {
+    Ok(match device_type {
+      SDL_TOUCH_DEVICE_DIRECT => Self::Direct,
+      SDL_TOUCH_DEVICE_INDIRECT_ABSOLUTE => Self::IndirectAbsolute,
+      SDL_TOUCH_DEVICE_INDIRECT_RELATIVE => Self::IndirectRelative,
+      _ => return Err(()),
+    })
+  }
+}
+
+/// A finger that's currently down on a touch device.
+///
+/// The position is normalized to `0.0 ..= 1.0`, the same as in a
+/// [`TouchFingerEvent`](crate::TouchFingerEvent).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Finger {
+  pub finger_id: FingerID,
+  pub x: f32,
+  pub y: f32,
+  pub pressure: f32,
+}
+impl From<SDL_Finger> for Finger {
+  #[inline]
+  fn from(finger: SDL_Finger) -> Self {
+    Self {
+      finger_id: FingerID(finger.id),
+      x: finger.x,
+      y: finger.y,
+      pressure: finger.pressure,
+    }
+  }
+}
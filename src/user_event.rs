@@ -0,0 +1,261 @@
+use core::{
+  any::Any,
+  cell::UnsafeCell,
+  cmp::Ordering,
+  marker::PhantomData,
+  sync::atomic::{self, AtomicBool},
+};
+
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+use fermium::{SDL_Event, SDL_UserEvent};
+
+use crate::{sdl_get_error, SdlError};
+
+/// Marks user events where `data1` is a boxed payload from beryllium.
+const PAYLOAD_CODE: i32 = 0x6265_7279;
+
+type Payload = Arc<dyn Any + Send + Sync>;
+
+/// The addresses of every payload that's been pushed but not yet taken.
+///
+/// The `code` and `data1` of an event are just numbers that anyone can copy or
+/// fake, so a payload pointer is only trusted if it's listed here. Whoever
+/// removes a pointer from the list is the one that frees it.
+struct LivePayloads {
+  locked: AtomicBool,
+  addresses: UnsafeCell<Vec<usize>>,
+}
+// Safety: the addresses are only touched while the lock is held.
+unsafe impl Sync for LivePayloads {}
+impl LivePayloads {
+  /// Runs `f` with the lock held.
+  ///
+  /// Note: This is a spin lock because we don't have `std`. It's only
+  /// ever held for a short list operation (or an `Arc` clone).
+  fn with<R>(&self, f: impl FnOnce(&mut Vec<usize>) -> R) -> R {
+    while self
+      .locked
+      .compare_exchange_weak(
+        false,
+        true,
+        atomic::Ordering::Acquire,
+        atomic::Ordering::Relaxed,
+      )
+      .is_err()
+    {
+      core::hint::spin_loop();
+    }
+    let out = f(unsafe { &mut *self.addresses.get() });
+    self.locked.store(false, atomic::Ordering::Release);
+    out
+  }
+
+  fn insert(&self, payload: *mut Payload) {
+    self.with(|addresses| addresses.push(payload as usize))
+  }
+
+  /// Removes a pointer from the list, giving if it was there.
+  fn remove(&self, payload: *mut Payload) -> bool {
+    self.with(|addresses| {
+      match addresses.iter().position(|&a| a == payload as usize) {
+        Some(i) => {
+          addresses.swap_remove(i);
+          true
+        }
+        None => false,
+      }
+    })
+  }
+}
+
+static LIVE_PAYLOADS: LivePayloads = LivePayloads {
+  locked: AtomicBool::new(false),
+  addresses: UnsafeCell::new(Vec::new()),
+};
+
+/// Frees every payload that never came back out of the queue.
+///
+/// This must only be called once SDL has shut down, so that none of those
+/// events can be read any more.
+pub(crate) fn free_live_payloads() {
+  let addresses = LIVE_PAYLOADS.with(core::mem::take);
+  addresses.into_iter().for_each(|address| {
+    drop(unsafe { Box::from_raw(address as *mut Payload) })
+  });
+}
+
+/// A registered user event type that carries values of type `T`.
+///
+/// Get one from [`Sdl::register_user_event`](crate::Sdl::register_user_event).
+/// This is `Send` and `Sync`, so it can be given to worker threads, which can
+/// then wake up a main loop that's blocked in `wait_event`.
+pub struct UserEventType<T> {
+  event_type: u32,
+  _marker: PhantomData<fn(T)>,
+}
+impl<T> Clone for UserEventType<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+impl<T> Copy for UserEventType<T> {}
+impl<T> core::fmt::Debug for UserEventType<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "UserEventType({})", self.event_type)
+  }
+}
+impl<T: Any + Send + Sync> UserEventType<T> {
+  pub(crate) fn register() -> Result<Self, SdlError> {
+    let event_type = unsafe { fermium::SDL_RegisterEvents(1) };
+    if event_type != u32::MAX {
+      Ok(Self { event_type, _marker: PhantomData })
+    } else {
+      Err(SdlError(Box::new(String::from(
+        "beryllium: no more user event types available.",
+      ))))
+    }
+  }
+
+  /// The SDL event type number that was registered.
+  pub fn event_type(&self) -> u32 {
+    self.event_type
+  }
+
+  /// Pushes a value onto the event queue, to come back out as an
+  /// [`Event::User`](crate::Event::User).
+  ///
+  /// This can be called from any thread.
+  ///
+  /// ## Failure
+  /// * If the queue is full, or SDL isn't running.
+  /// * If an event filter dropped the event.
+  pub fn push(&self, value: T) -> Result<(), SdlError> {
+    let payload: Payload = Arc::new(value);
+    let data1 = Box::into_raw(Box::new(payload));
+    // Note: This has to happen before the push, since another thread
+    // could pull the event out again before `SDL_PushEvent` even returns.
+    LIVE_PAYLOADS.insert(data1);
+    let mut sdl_event = SDL_Event::default();
+    sdl_event.user = SDL_UserEvent {
+      type_: self.event_type,
+      timestamp: 0,
+      windowID: 0,
+      code: PAYLOAD_CODE,
+      data1: data1.cast(),
+      data2: core::ptr::null_mut(),
+    };
+    let ret = unsafe { fermium::SDL_PushEvent(&mut sdl_event) };
+    if ret > 0 {
+      Ok(())
+    } else {
+      // The event never made it into the queue, so we still own this (unless
+      // SDL was shutting down and already freed it).
+      if LIVE_PAYLOADS.remove(data1) {
+        drop(unsafe { Box::from_raw(data1) });
+      }
+      if ret == 0 {
+        Err(SdlError(Box::new(String::from(
+          "beryllium: the user event was filtered out.",
+        ))))
+      } else {
+        Err(sdl_get_error())
+      }
+    }
+  }
+}
+
+/// A user event that was pushed with a [`UserEventType`].
+///
+/// Check the `event_type` (or just try a downcast) to see which of your types
+/// it holds.
+///
+/// * Payloads are freed when their event is taken out of the queue, or when it
+///   gets flushed with [`Sdl::flush_events`](crate::Sdl::flush_events).
+/// * A push that an event filter rejects is freed right away.
+/// * Anything still in the queue when SDL shuts down is freed then.
+/// * If you remove queued events with raw `fermium` calls (such as
+///   `SDL_FlushEvents`), their payloads stay alive until SDL shuts down.
+/// * Clones share the same payload.
+#[derive(Debug, Clone)]
+pub struct UserEvent {
+  pub event_type: u32,
+  payload: Payload,
+}
+impl UserEvent {
+  /// Takes ownership of the payload out of an SDL user event, if it has one.
+  ///
+  /// Only a live payload that beryllium pushed is taken. Any other pointer,
+  /// including one that was already taken through a copy of the same event,
+  /// gives `None`.
+  pub(crate) fn take_from_sdl(user: &SDL_UserEvent) -> Option<Self> {
+    let data1 = user.data1 as *mut Payload;
+    if user.code != PAYLOAD_CODE || !LIVE_PAYLOADS.remove(data1) {
+      return None;
+    }
+    // Safety: it was in the list, and we just took it out.
+    let payload = unsafe { Box::from_raw(data1) };
+    Some(Self { event_type: user.type_, payload: *payload })
+  }
+
+  /// Clones the payload of an SDL user event, if it has one, leaving the
+  /// event itself still owning it.
+  ///
+  /// Only a live payload that beryllium pushed is cloned.
+  pub(crate) fn clone_from_sdl(user: &SDL_UserEvent) -> Option<Self> {
+    let data1 = user.data1 as *const Payload;
+    if user.code != PAYLOAD_CODE {
+      return None;
+    }
+    // Note: The clone happens with the lock held, so the payload
+    // can't be taken and freed out from under us.
+    LIVE_PAYLOADS.with(|addresses| {
+      if addresses.contains(&(data1 as usize)) {
+        // Safety: it's in the list, so it's still alive.
+        let payload = unsafe { &*data1 };
+        Some(Self { event_type: user.type_, payload: payload.clone() })
+      } else {
+        None
+      }
+    })
+  }
+
+  /// If the payload is a `T`.
+  pub fn is<T: Any>(&self) -> bool {
+    self.payload.is::<T>()
+  }
+
+  /// A reference to the payload, if it's a `T`.
+  pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+    self.payload.downcast_ref::<T>()
+  }
+
+  /// Moves the payload out, if it's a `T` and this is the only clone of the
+  /// event. Otherwise you get the event back.
+  pub fn into_inner<T: Any + Send + Sync>(self) -> Result<T, Self> {
+    let event_type = self.event_type;
+    match self.payload.downcast::<T>() {
+      Ok(arc) => {
+        Arc::try_unwrap(arc).map_err(|payload| Self { event_type, payload })
+      }
+      Err(payload) => Err(Self { event_type, payload }),
+    }
+  }
+}
+impl PartialEq for UserEvent {
+  fn eq(&self, other: &Self) -> bool {
+    self.event_type == other.event_type
+      && Arc::ptr_eq(&self.payload, &other.payload)
+  }
+}
+impl PartialOrd for UserEvent {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    if self == other {
+      Some(Ordering::Equal)
+    } else if self.event_type != other.event_type {
+      self.event_type.partial_cmp(&other.event_type)
+    } else {
+      None
+    }
+  }
+}
@@ -6,7 +6,9 @@ use tinyvec::TinyVec;
 
 use fermium::SDL_Window;
 
-use crate::{sdl_get_error, Initialization, SdlError};
+use crate::{
+  sdl_get_error, Initialization, MouseMode, MouseModeGuard, SdlError, WindowID,
+};
 
 /// The "superclass" for the SDL window types.
 ///
@@ -54,6 +56,41 @@ impl Window {
     .map(|nn| Window { init, nn })
   }
 
+  /// The ID of this window, which is how events say what window they're for.
+  pub fn id(&self) -> WindowID {
+    WindowID(unsafe { fermium::SDL_GetWindowID(self.nn.as_ptr()) })
+  }
+
+  /// If the on screen keyboard is showing for this window.
+  ///
+  /// See [`Sdl::has_screen_keyboard_support`](crate::Sdl::has_screen_keyboard_support).
+  pub fn is_screen_keyboard_shown(&self) -> bool {
+    let shown = unsafe { fermium::SDL_IsScreenKeyboardShown(self.as_ptr()) };
+    shown == fermium::SDL_TRUE
+  }
+
+  /// Moves the mouse cursor to a position in this window.
+  ///
+  /// This makes a mouse motion event, except in relative mouse mode.
+  pub fn warp_mouse(&self, [x, y]: [i32; 2]) {
+    unsafe { fermium::SDL_WarpMouseInWindow(self.as_ptr(), x, y) }
+  }
+
+  /// Sets how the mouse behaves over this window.
+  ///
+  /// This sets the window's mouse grab, relative mouse mode, and cursor
+  /// visibility together, so they can't get out of step (such as leaving the
+  /// cursor hidden once relative mode is off). They all go back to how they
+  /// were when the guard drops.
+  ///
+  /// ## Failure
+  /// * If the platform can't do relative mode.
+  pub fn set_mouse_mode(
+    &self, mode: MouseMode,
+  ) -> Result<MouseModeGuard<'_>, SdlError> {
+    MouseModeGuard::new(self, mode)
+  }
+
   pub(crate) fn as_ptr(&self) -> *mut SDL_Window {
     self.nn.as_ptr()
   }
@@ -0,0 +1,121 @@
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::{convert_pixels, PixelFormatEnum, SdlError, Surface};
+
+/// The pitch (bytes per row) of the Y plane for a tightly packed YUV image.
+///
+/// Gives `None` if the format isn't a YUV format.
+pub fn yuv_pitch(format: PixelFormatEnum, width: usize) -> Option<usize> {
+  match format {
+    PixelFormatEnum::YV12
+    | PixelFormatEnum::IYUV
+    | PixelFormatEnum::NV12
+    | PixelFormatEnum::NV21 => Some(width),
+    PixelFormatEnum::YUY2 | PixelFormatEnum::UYVY | PixelFormatEnum::YVYU => {
+      Some(4 * half_rounded_up(width))
+    }
+    _ => None,
+  }
+}
+
+/// The total bytes of a YUV image, counting every plane.
+///
+/// * `y_pitch` is the pitch of the Y plane (or the only plane, for packed
+///   formats). The chroma planes of planar formats have their pitch and height
+///   rounded up from half of the Y plane's, the same as SDL expects.
+///
+/// Gives `None` if the format isn't a YUV format.
+pub fn yuv_frame_len(
+  format: PixelFormatEnum, y_pitch: usize, height: usize,
+) -> Option<usize> {
+  let y_plane = y_pitch * height;
+  let half_pitch = half_rounded_up(y_pitch);
+  let half_height = half_rounded_up(height);
+  match format {
+    // Two half size planes, or one interleaved plane that's twice as wide.
+    PixelFormatEnum::YV12
+    | PixelFormatEnum::IYUV
+    | PixelFormatEnum::NV12
+    | PixelFormatEnum::NV21 => Some(y_plane + 2 * half_pitch * half_height),
+    PixelFormatEnum::YUY2 | PixelFormatEnum::UYVY | PixelFormatEnum::YVYU => {
+      Some(y_plane)
+    }
+    _ => None,
+  }
+}
+
+fn half_rounded_up(n: usize) -> usize {
+  n / 2 + n % 2
+}
+
+fn not_yuv() -> SdlError {
+  SdlError(Box::new(String::from("beryllium: that's not a YUV format.")))
+}
+
+impl Surface {
+  /// Makes a new RGB surface from YUV image data (eg: a decoded video frame).
+  ///
+  /// * `y_pitch` is the pitch of the Y plane, see [`yuv_frame_len`] for how
+  ///   the other planes are laid out.
+  /// * `rgb_format` is the format of the new surface.
+  ///
+  /// ## Failure
+  /// * If `yuv_format` isn't a YUV format.
+  ///
+  /// ## Panics
+  /// * If `y_pitch` is too small to hold `width` pixels.
+  /// * If `yuv` is shorter than the full frame.
+  pub fn from_yuv(
+    yuv: &[u8], yuv_format: PixelFormatEnum, y_pitch: usize, width: usize,
+    height: usize, rgb_format: PixelFormatEnum,
+  ) -> Result<Self, SdlError> {
+    let min_pitch = yuv_pitch(yuv_format, width).ok_or_else(not_yuv)?;
+    assert!(y_pitch >= min_pitch, "YUV pitch is too small");
+    let len = yuv_frame_len(yuv_format, y_pitch, height).ok_or_else(not_yuv)?;
+    assert!(yuv.len() >= len, "YUV buffer too short for the frame");
+    let bit_depth = fermium::SDL_BITSPERPIXEL(rgb_format.0 as _) as usize;
+    let mut surface =
+      Surface::new_with_format(width, height, bit_depth, rgb_format)?;
+    let mut lock = surface.lock()?;
+    let pitch = lock.pitch();
+    convert_pixels(
+      yuv_format,
+      yuv,
+      y_pitch,
+      rgb_format,
+      lock.pixels_mut(),
+      pitch,
+      [width, height],
+    )?;
+    drop(lock);
+    Ok(surface)
+  }
+
+  /// Converts the surface into a tightly packed YUV image.
+  ///
+  /// The Y plane pitch of the output is [`yuv_pitch`], and the whole buffer is
+  /// [`yuv_frame_len`] bytes.
+  ///
+  /// ## Failure
+  /// * If `yuv_format` isn't a YUV format.
+  pub fn to_yuv(
+    &self, yuv_format: PixelFormatEnum,
+  ) -> Result<Vec<u8>, SdlError> {
+    let (width, height) = (self.width(), self.height());
+    let y_pitch = yuv_pitch(yuv_format, width).ok_or_else(not_yuv)?;
+    let len = yuv_frame_len(yuv_format, y_pitch, height).ok_or_else(not_yuv)?;
+    let mut yuv = vec![0_u8; len];
+    self.with_pixels(|pixels| {
+      convert_pixels(
+        self.pixel_format_enum(),
+        pixels,
+        self.pitch(),
+        yuv_format,
+        &mut yuv,
+        y_pitch,
+        [width, height],
+      )
+    })??;
+    Ok(yuv)
+  }
+}